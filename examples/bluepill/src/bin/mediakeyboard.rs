@@ -5,8 +5,9 @@ use bluepill_examples as _;
 use cortex_m::asm;
 use defmt::info;
 use infrared::{
-    protocol::AppleNec,
-    remotecontrol::{nec::Apple2009, Action, Button},
+    protocol::{nec::AppleNecCommand, AppleNec},
+    receiver::{keys::RELEASE_TIMEOUT, KeyEvent, KeyEvents},
+    remotecontrol::{nec::Apple2009, Action, RemoteControlModel},
     Receiver,
 };
 use stm32f1xx_hal::{
@@ -31,8 +32,8 @@ mod app {
     /// The pin connected to the infrared receiver module
     type IrPin = PB8<Input<Floating>>;
     type IrProto = AppleNec;
-    type IrRemote = Apple2009;
-    type IrReceiver = Receiver<IrProto, IrPin, TimerInstantU32<TIM_FREQ>, Button<IrRemote>>;
+    type IrReceiver = Receiver<IrProto, IrPin, TimerInstantU32<TIM_FREQ>>;
+    type IrKeys = KeyEvents<AppleNecCommand, TimerInstantU32<TIM_FREQ>>;
 
     #[monotonic(binds = TIM3, default = true)]
     type Monotonic = stm32f1xx_hal::timer::MonoTimer<pac::TIM3, TIM_FREQ>;
@@ -41,6 +42,7 @@ mod app {
     struct Shared {
         usb_dev: UsbDevice<'static, UsbBusType>,
         usb_kbd: HIDClass<'static, UsbBusType>,
+        keys: IrKeys,
     }
 
     #[local]
@@ -102,7 +104,11 @@ mod app {
         let mono = cx.device.TIM3.monotonic(&clocks);
 
         (
-            Shared { usb_dev, usb_kbd },
+            Shared {
+                usb_dev,
+                usb_kbd,
+                keys: KeyEvents::new(TIM_FREQ),
+            },
             Local { receiver },
             init::Monotonics(mono),
         )
@@ -126,28 +132,39 @@ mod app {
         });
     }
 
-    #[task(binds = EXTI9_5, local = [receiver])]
-    fn ir_rx(cx: ir_rx::Context) {
+    #[task(binds = EXTI9_5, local = [receiver], shared = [keys])]
+    fn ir_rx(mut cx: ir_rx::Context) {
         let now = monotonics::Monotonic::now();
         let receiver = cx.local.receiver;
 
-        if let Ok(Some(button)) = receiver.event_instant(now) {
-            if let Some(action) = button.action() {
-                info!("{:?}", button);
-                let key = super::action_to_mediakey(action);
-                info!("{:?}", defmt::Debug2Format(&key));
-                keydown::spawn(key).unwrap();
-            }
+        if let Ok(Some(cmd)) = receiver.event_instant(now) {
+            cx.shared.keys.lock(|keys| {
+                for event in keys.command(cmd, now) {
+                    super::key_event(event);
+                }
+            });
         }
 
         receiver.pin_mut().clear_interrupt_pending_bit();
     }
 
+    /// Release the key when the remote stops sending
+    #[task(shared = [keys])]
+    fn key_timeout(mut cx: key_timeout::Context) {
+        let now = monotonics::Monotonic::now();
+
+        cx.shared.keys.lock(|keys| match keys.poll(now) {
+            Some(event) => super::key_event(event),
+            None if keys.pressed().is_some() => {
+                key_timeout::spawn_after(RELEASE_TIMEOUT.micros()).ok();
+            }
+            None => (),
+        });
+    }
+
     #[task(shared = [usb_kbd])]
     fn keydown(mut cx: keydown::Context, key: MediaKey) {
         cx.shared.usb_kbd.lock(|kbd| super::send_keycode(kbd, key));
-
-        keyup::spawn_after(20.millis()).unwrap();
     }
 
     #[task(shared = [usb_kbd])]
@@ -158,6 +175,26 @@ mod app {
     }
 }
 
+fn key_event<Dur>(event: KeyEvent<AppleNecCommand, Dur>) {
+    match event {
+        KeyEvent::Pressed(cmd) => {
+            if let Some(action) = Apple2009::decode(&cmd) {
+                let key = action_to_mediakey(action);
+                info!("{:?}", defmt::Debug2Format(&key));
+                app::keydown::spawn(key).ok();
+                app::key_timeout::spawn_after(RELEASE_TIMEOUT.micros()).ok();
+            }
+        }
+        KeyEvent::Held(..) => (),
+        KeyEvent::Released(cmd) => {
+            // Only the commands with an action pressed a key
+            if Apple2009::decode(&cmd).is_some() {
+                app::keyup::spawn().ok();
+            }
+        }
+    }
+}
+
 fn usb_poll<B: bus::UsbBus>(
     usb_dev: &mut UsbDevice<'static, B>,
     usb_kbd: &mut HIDClass<'static, B>,
@@ -4,6 +4,11 @@ use crate::ProtocolId;
 pub trait Command {
     /// True if command is a repeat
     fn is_repeat(&self) -> bool;
+
+    /// Toggle bit, flipped for every new key press. `None` for protocols without one
+    fn toggle(&self) -> Option<bool> {
+        None
+    }
 }
 
 /// Command with address and command part
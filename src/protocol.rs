@@ -1,32 +1,87 @@
 //! Infrared protocol
 
+#[cfg(feature = "ac")]
+pub mod ac;
 #[cfg(feature = "denon")]
 pub mod denon;
+#[cfg(feature = "grundig")]
+pub mod grundig;
+#[cfg(feature = "hash")]
+pub mod hash;
+#[cfg(feature = "jvc")]
+pub mod jvc;
+#[cfg(feature = "kaseikyo")]
+pub mod kaseikyo;
+#[cfg(feature = "lego_pf")]
+pub mod lego_pf;
 #[cfg(feature = "nec")]
 pub mod nec;
+#[cfg(feature = "nrc17")]
+pub mod nrc17;
 #[cfg(feature = "rc5")]
 pub mod rc5;
 #[cfg(feature = "rc6")]
 pub mod rc6;
+#[cfg(feature = "rcmm")]
+pub mod rcmm;
 #[cfg(feature = "sbp")]
 pub mod sbp;
+#[cfg(feature = "sharp")]
+pub mod sharp;
+#[cfg(feature = "sirc")]
+pub mod sirc;
+#[cfg(feature = "xmp")]
+pub mod xmp;
 
 #[cfg(feature = "denon")]
 #[doc(inline)]
 pub use denon::Denon;
+#[cfg(feature = "grundig")]
+#[doc(inline)]
+pub use grundig::Grundig;
+#[cfg(feature = "hash")]
+#[doc(inline)]
+pub use hash::Hash;
+#[cfg(feature = "jvc")]
+#[doc(inline)]
+pub use jvc::Jvc;
+#[cfg(feature = "kaseikyo")]
+#[doc(inline)]
+pub use kaseikyo::Kaseikyo;
+#[cfg(feature = "lego_pf")]
+#[doc(inline)]
+pub use lego_pf::LegoPf;
 #[cfg(feature = "nec")]
 #[doc(inline)]
-pub use nec::{AppleNec, Nec, Nec16, NecDebug, SamsungNec};
+pub use nec::{AppleNec, Nec, Nec16, Nec42, NecDebug, NecExt, SamsungNec};
+#[cfg(feature = "nrc17")]
+#[doc(inline)]
+pub use nrc17::Nrc17;
 #[cfg(feature = "rc5")]
 #[doc(inline)]
 pub use rc5::Rc5;
 #[cfg(feature = "rc6")]
 #[doc(inline)]
 pub use rc6::Rc6;
+#[cfg(feature = "rcmm")]
+#[doc(inline)]
+pub use rcmm::RcMm;
 #[cfg(feature = "sbp")]
 #[doc(inline)]
-pub use sbp::Sbp;
+pub use sbp::{Samsung36, Sbp};
+#[cfg(feature = "sharp")]
+#[doc(inline)]
+pub use sharp::Sharp;
+#[cfg(feature = "sirc")]
+#[doc(inline)]
+pub use sirc::Sirc;
+#[cfg(feature = "xmp")]
+#[doc(inline)]
+pub use xmp::Xmp;
 
+pub mod biphase;
+pub mod pulsedistance;
+pub mod pulsewidth;
 pub(crate) mod utils;
 
 /// Infrared protocol
@@ -56,6 +111,30 @@ pub enum ProtocolId {
     Sbp = 7,
     /// Denon
     Denon = 8,
+    /// Sony SIRC, 12, 15 and 20 bit
+    Sirc = 9,
+    /// JVC
+    Jvc = 10,
+    /// Kaseikyo 48 bit (Panasonic and others)
+    Kaseikyo = 11,
+    /// Sharp
+    Sharp = 12,
+    /// Nokia NRC17
+    Nrc17 = 13,
+    /// Grundig
+    Grundig = 14,
+    /// Nec with 16 bit address and 16 bit command
+    NecExt = 15,
+    /// Nec42
+    Nec42 = 16,
+    /// Philips RC-MM, 12, 24 and 32 bit
+    RcMm = 17,
+    /// Microsoft XMP
+    Xmp = 18,
+    /// Lego Power Functions
+    LegoPf = 19,
+    /// Hash of an unknown protocol
+    Hash = 20,
     /// Placeholder
     Unknown = 255,
 }
@@ -71,6 +150,18 @@ impl From<u8> for ProtocolId {
             6 => ProtocolId::Rc6,
             7 => ProtocolId::Sbp,
             8 => ProtocolId::Denon,
+            9 => ProtocolId::Sirc,
+            10 => ProtocolId::Jvc,
+            11 => ProtocolId::Kaseikyo,
+            12 => ProtocolId::Sharp,
+            13 => ProtocolId::Nrc17,
+            14 => ProtocolId::Grundig,
+            15 => ProtocolId::NecExt,
+            16 => ProtocolId::Nec42,
+            17 => ProtocolId::RcMm,
+            18 => ProtocolId::Xmp,
+            19 => ProtocolId::LegoPf,
+            20 => ProtocolId::Hash,
             _ => ProtocolId::Unknown,
         }
     }
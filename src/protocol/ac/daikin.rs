@@ -0,0 +1,138 @@
+//! Daikin air conditioner, 216 bit frames
+//!
+//! Two sections, 8 and 19 bytes, both starting with `11 DA 27` and ending with the sum of the
+//! other bytes in the section.
+
+use crate::{
+    protocol::{
+        ac::{
+            checksum,
+            encoder::{encode, encoder_data},
+            AcCommand, AcDecoder, AcFrame, AcMode, AcProtocol, AcState, FanSpeed, Section,
+        },
+        Protocol,
+    },
+    receiver::{time::InfraMonotonic, DecoderBuilder},
+    sender::ProtocolEncoder,
+};
+
+const NBYTES: usize = 27;
+/// Start of the second section
+const SECOND: usize = 8;
+
+const FRAME: AcFrame = AcFrame {
+    header_high: 3440,
+    header_low: 1720,
+    data_high: 430,
+    zero_low: 430,
+    one_low: 1300,
+    gap: 29_650,
+    sections: &[
+        Section {
+            header: true,
+            nbytes: SECOND as u8,
+            footer_bits: 0,
+            footer: 0,
+        },
+        Section {
+            header: true,
+            nbytes: (NBYTES - SECOND) as u8,
+            footer_bits: 0,
+            footer: 0,
+        },
+    ],
+};
+
+const SIGNATURE: [u8; 3] = [0x11, 0xDA, 0x27];
+const POWER: u8 = 0x01;
+const SWING: u8 = 0x0F;
+
+/// Daikin air conditioner
+pub struct Daikin;
+
+impl Protocol for Daikin {
+    type Cmd = AcCommand<NBYTES>;
+}
+
+impl AcProtocol<NBYTES> for Daikin {
+    const FRAME: &'static AcFrame = &FRAME;
+    #[rustfmt::skip]
+    const TEMPLATE: [u8; NBYTES] = [
+        0x11, 0xDA, 0x27, 0xF0, 0x00, 0x00, 0x00, 0x00,
+        0x11, 0xDA, 0x27, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    fn validate(bytes: &[u8; NBYTES]) -> bool {
+        bytes[..3] == SIGNATURE
+            && bytes[SECOND..SECOND + 3] == SIGNATURE
+            && checksum(&bytes[..SECOND - 1]) == bytes[SECOND - 1]
+            && checksum(&bytes[SECOND..NBYTES - 1]) == bytes[NBYTES - 1]
+    }
+
+    fn state(bytes: &[u8; NBYTES]) -> Option<AcState> {
+        let mode = match bytes[13] >> 4 & 0x07 {
+            0 => AcMode::Auto,
+            2 => AcMode::Dry,
+            3 => AcMode::Cool,
+            4 => AcMode::Heat,
+            6 => AcMode::Fan,
+            _ => return None,
+        };
+
+        let fan = match bytes[16] >> 4 {
+            0xA => FanSpeed::Auto,
+            3 | 4 => FanSpeed::Low,
+            5 => FanSpeed::Medium,
+            6 | 7 => FanSpeed::High,
+            _ => return None,
+        };
+
+        Some(AcState {
+            power: bytes[13] & POWER != 0,
+            mode,
+            temperature: bytes[14] / 2,
+            fan,
+            swing: bytes[16] & SWING == SWING,
+        })
+    }
+
+    fn apply(state: &AcState, bytes: &mut [u8; NBYTES]) {
+        let mode = match state.mode {
+            AcMode::Auto => 0,
+            AcMode::Dry => 2,
+            AcMode::Cool => 3,
+            AcMode::Heat => 4,
+            AcMode::Fan => 6,
+        };
+        let fan = match state.fan {
+            FanSpeed::Auto => 0xA,
+            FanSpeed::Low => 3,
+            FanSpeed::Medium => 5,
+            FanSpeed::High => 7,
+        };
+
+        bytes[13] = bytes[13] & !0x71 | mode << 4 | u8::from(state.power);
+        bytes[14] = state.temperature.clamp(10, 32) * 2;
+        bytes[16] = fan << 4 | if state.swing { SWING } else { 0 };
+        bytes[SECOND - 1] = checksum(&bytes[..SECOND - 1]);
+        bytes[NBYTES - 1] = checksum(&bytes[SECOND..NBYTES - 1]);
+    }
+}
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Daikin {
+    type Decoder = AcDecoder<Mono, Daikin, NBYTES>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        AcDecoder::new(freq)
+    }
+}
+
+impl<const F: u32> ProtocolEncoder<F> for Daikin {
+    type EncoderData = [u32; 6];
+    const DATA: [u32; 6] = encoder_data(&FRAME, F);
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        encode::<Self, NBYTES>(&<Self as ProtocolEncoder<F>>::DATA, cmd, b)
+    }
+}
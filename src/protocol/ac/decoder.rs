@@ -0,0 +1,199 @@
+use core::marker::PhantomData;
+
+use crate::{
+    protocol::ac::{AcFrame, AcProtocol, Layout},
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecodingError, ProtocolDecoder, State,
+    },
+};
+
+const DATA_HIGH: usize = 0;
+const ZERO_LOW: usize = 1;
+const ONE_LOW: usize = 2;
+const HEADER_HIGH: usize = 3;
+const HEADER_LOW: usize = 4;
+const GAP: usize = 5;
+
+const fn pulselens(frame: &AcFrame) -> [u32; 8] {
+    [
+        frame.data_high,
+        frame.zero_low,
+        frame.one_low,
+        frame.header_high,
+        frame.header_low,
+        frame.gap,
+        0,
+        0,
+    ]
+}
+
+const TOL: [u32; 8] = [40, 40, 25, 15, 15, 20, 0, 0];
+
+/// Decoder for the air conditioner protocols, into a `N` byte buffer
+pub struct AcDecoder<Mono: InfraMonotonic, Proto, const N: usize> {
+    state: AcDecoderState,
+    bytes: [u8; N],
+    /// Offset of the current section in `bytes`
+    offset: usize,
+    /// Received footer bits
+    footer: u8,
+    dt_save: Mono::Duration,
+    spans: PulseSpans<Mono>,
+    proto: PhantomData<Proto>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AcDecoderState {
+    Idle,
+    /// Waiting for the header of the section
+    Header(u8),
+    /// Section and number of bits received in it
+    Data(u8, u16),
+    /// Section received, waiting for the stop mark and the gap
+    Gap(u8),
+    Done,
+    Err(DecodingError),
+}
+
+impl From<AcDecoderState> for State {
+    fn from(state: AcDecoderState) -> Self {
+        match state {
+            AcDecoderState::Idle => State::Idle,
+            AcDecoderState::Done => State::Done,
+            AcDecoderState::Err(err) => State::Error(err),
+            _ => State::Receiving,
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic, Proto: AcProtocol<N>, const N: usize> AcDecoder<Mono, Proto, N> {
+    /// Create a decoder for a sample rate of `freq`
+    pub fn new(freq: u32) -> Self {
+        let () = Layout::<Proto, N>::CHECK;
+
+        AcDecoder {
+            state: AcDecoderState::Idle,
+            bytes: [0; N],
+            offset: 0,
+            footer: 0,
+            dt_save: Mono::ZERO_DURATION,
+            spans: PulseSpans::new(freq, &pulselens(Proto::FRAME), &TOL),
+            proto: PhantomData,
+        }
+    }
+
+    fn is_header(&self, mark: Mono::Duration, space: Mono::Duration) -> bool {
+        self.spans.matches(HEADER_HIGH, mark) && self.spans.matches(HEADER_LOW, space)
+    }
+
+    fn bit(&self, mark: Mono::Duration, space: Mono::Duration) -> Option<bool> {
+        if !self.spans.matches(DATA_HIGH, mark) {
+            None
+        } else if self.spans.matches(ZERO_LOW, space) {
+            Some(false)
+        } else if self.spans.matches(ONE_LOW, space) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    /// Store bit `n` of `section`
+    fn store(&mut self, section: u8, n: u16, one: bool) -> AcDecoderState {
+        let sect = &Proto::FRAME.sections[usize::from(section)];
+        let nbits = u16::from(sect.nbytes) * 8;
+
+        if one {
+            if n < nbits {
+                if let Some(byte) = self.bytes.get_mut(self.offset + usize::from(n / 8)) {
+                    *byte |= 1 << (n % 8);
+                }
+            } else {
+                self.footer |= 1 << (n - nbits);
+            }
+        }
+
+        if n + 1 < sect.nbits() {
+            return AcDecoderState::Data(section, n + 1);
+        }
+
+        if self.footer != sect.footer {
+            return AcDecoderState::Err(DecodingError::Validation);
+        }
+
+        self.offset += usize::from(sect.nbytes);
+        self.footer = 0;
+
+        if usize::from(section) + 1 == Proto::FRAME.sections.len() {
+            AcDecoderState::Done
+        } else {
+            AcDecoderState::Gap(section)
+        }
+    }
+}
+
+impl<Mono, Proto, const N: usize> ProtocolDecoder<Proto, Mono> for AcDecoder<Mono, Proto, N>
+where
+    Mono: InfraMonotonic,
+    Proto: AcProtocol<N>,
+{
+    #[rustfmt::skip]
+    fn event(&mut self, rising: bool, dt: Mono::Duration) -> State {
+        use AcDecoderState::*;
+
+        if rising {
+            let mark = self.dt_save;
+            let sections = Proto::FRAME.sections;
+
+            self.state = match self.state {
+                Idle if self.is_header(mark, dt) => {
+                    self.bytes = [0; N];
+                    self.offset = 0;
+                    self.footer = 0;
+                    Data(0, 0)
+                }
+                Idle                            => Idle,
+
+                Header(s) if self.is_header(mark, dt) => Data(s, 0),
+                Header(_)                       => Err(DecodingError::Data),
+
+                Data(s, n) => match self.bit(mark, dt) {
+                    Some(one)                   => self.store(s, n, one),
+                    None                        => Err(DecodingError::Data),
+                },
+
+                Gap(s) if self.spans.matches(GAP, dt) => {
+                    if sections[usize::from(s) + 1].header { Header(s + 1) } else { Data(s + 1, 0) }
+                }
+                Gap(_)                          => Err(DecodingError::Data),
+
+                Done                            => Done,
+                Err(err)                        => Err(err),
+            };
+
+            self.dt_save = Mono::ZERO_DURATION;
+        } else {
+            self.dt_save = dt;
+        }
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<Proto::Cmd> {
+        match self.state {
+            AcDecoderState::Done => Proto::unpack(&self.bytes),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = AcDecoderState::Idle;
+        self.dt_save = Mono::ZERO_DURATION;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.spans
+    }
+}
@@ -0,0 +1,74 @@
+use crate::protocol::{
+    ac::{AcFrame, AcProtocol, Layout},
+    utils::scale_with_samplerate,
+};
+
+/// Lengths of the pulses in ticks of a `freq` Hz clock, used as `ProtocolEncoder::DATA`
+pub const fn encoder_data(frame: &AcFrame, freq: u32) -> [u32; 6] {
+    [
+        scale_with_samplerate(frame.header_high, freq),
+        scale_with_samplerate(frame.header_low, freq),
+        scale_with_samplerate(frame.data_high, freq),
+        scale_with_samplerate(frame.zero_low, freq),
+        scale_with_samplerate(frame.one_low, freq),
+        scale_with_samplerate(frame.gap, freq),
+    ]
+}
+
+/// Encode `cmd` into `b`, with the pulse lengths from [`encoder_data`]. The state of the
+/// command is written to the bytes before they are sent
+///
+/// `b` needs [`AcFrame::buffer_len`] entries, nothing is encoded and 0 returned if it's shorter
+pub fn encode<Proto: AcProtocol<N>, const N: usize>(
+    data: &[u32; 6],
+    cmd: &Proto::Cmd,
+    b: &mut [u32],
+) -> usize {
+    let () = Layout::<Proto, N>::CHECK;
+
+    if b.len() < Proto::FRAME.buffer_len() {
+        return 0;
+    }
+
+    let [header_high, header_low, data_high, zero_low, one_low, gap] = *data;
+
+    let mut bytes = cmd.bytes;
+    Proto::apply(&cmd.state, &mut bytes);
+
+    b[0] = 0;
+    let mut bi = 1;
+    let mut offset = 0;
+
+    for (i, sect) in Proto::FRAME.sections.iter().enumerate() {
+        if i > 0 {
+            b[bi] = gap;
+            bi += 1;
+        }
+
+        if sect.header {
+            b[bi] = header_high;
+            b[bi + 1] = header_low;
+            bi += 2;
+        }
+
+        let nbytes = usize::from(sect.nbytes);
+        let data = bytes[offset..offset + nbytes]
+            .iter()
+            .flat_map(|byte| (0..8).map(move |n| (byte >> n) & 1 != 0));
+        let footer = (0..sect.footer_bits).map(|n| (sect.footer >> n) & 1 != 0);
+
+        for one in data.chain(footer) {
+            b[bi] = data_high;
+            b[bi + 1] = if one { one_low } else { zero_low };
+            bi += 2;
+        }
+
+        // Stop mark
+        b[bi] = data_high;
+        bi += 1;
+
+        offset += nbytes;
+    }
+
+    bi
+}
@@ -0,0 +1,137 @@
+//! Gree air conditioner
+//!
+//! Two sections of 4 bytes. The first section ends with the fixed bits `010` and there is no
+//! header before the second section. The high nibble of the last byte is a checksum.
+
+use crate::{
+    protocol::{
+        ac::{
+            encoder::{encode, encoder_data},
+            AcCommand, AcDecoder, AcFrame, AcMode, AcProtocol, AcState, FanSpeed, Section,
+        },
+        Protocol,
+    },
+    receiver::{time::InfraMonotonic, DecoderBuilder},
+    sender::ProtocolEncoder,
+};
+
+const NBYTES: usize = 8;
+
+const FRAME: AcFrame = AcFrame {
+    header_high: 9000,
+    header_low: 4500,
+    data_high: 620,
+    zero_low: 540,
+    one_low: 1600,
+    gap: 19_980,
+    sections: &[
+        Section {
+            header: true,
+            nbytes: 4,
+            footer_bits: 3,
+            footer: 0b010,
+        },
+        Section {
+            header: false,
+            nbytes: 4,
+            footer_bits: 0,
+            footer: 0,
+        },
+    ],
+};
+
+const POWER: u8 = 0x08;
+const SWING: u8 = 0x40;
+
+/// Low nibbles of the first four bytes and high nibbles of the next three, plus 10
+fn checksum(bytes: &[u8; NBYTES]) -> u8 {
+    let low = bytes[..4].iter().map(|b| b & 0x0F);
+    let high = bytes[4..7].iter().map(|b| b >> 4);
+
+    low.chain(high).fold(10u8, |sum, n| sum.wrapping_add(n)) & 0x0F
+}
+
+/// Gree air conditioner
+pub struct Gree;
+
+impl Protocol for Gree {
+    type Cmd = AcCommand<NBYTES>;
+}
+
+impl AcProtocol<NBYTES> for Gree {
+    const FRAME: &'static AcFrame = &FRAME;
+    const TEMPLATE: [u8; NBYTES] = [0x00, 0x00, 0x20, 0x50, 0x00, 0x20, 0x00, 0x00];
+
+    fn validate(bytes: &[u8; NBYTES]) -> bool {
+        checksum(bytes) == bytes[7] >> 4
+    }
+
+    fn state(bytes: &[u8; NBYTES]) -> Option<AcState> {
+        let mode = match bytes[0] & 0x07 {
+            0 => AcMode::Auto,
+            1 => AcMode::Cool,
+            2 => AcMode::Dry,
+            3 => AcMode::Fan,
+            4 => AcMode::Heat,
+            _ => return None,
+        };
+
+        let fan = match bytes[0] >> 4 & 0x03 {
+            0 => FanSpeed::Auto,
+            1 => FanSpeed::Low,
+            2 => FanSpeed::Medium,
+            _ => FanSpeed::High,
+        };
+
+        Some(AcState {
+            power: bytes[0] & POWER != 0,
+            mode,
+            temperature: 16 + (bytes[1] & 0x0F),
+            fan,
+            swing: bytes[0] & SWING != 0,
+        })
+    }
+
+    fn apply(state: &AcState, bytes: &mut [u8; NBYTES]) {
+        let mode = match state.mode {
+            AcMode::Auto => 0,
+            AcMode::Cool => 1,
+            AcMode::Dry => 2,
+            AcMode::Fan => 3,
+            AcMode::Heat => 4,
+        };
+        let fan = match state.fan {
+            FanSpeed::Auto => 0,
+            FanSpeed::Low => 1,
+            FanSpeed::Medium => 2,
+            FanSpeed::High => 3,
+        };
+
+        bytes[0] = bytes[0] & 0x80
+            | mode
+            | if state.power { POWER } else { 0 }
+            | fan << 4
+            | if state.swing { SWING } else { 0 };
+        bytes[1] = bytes[1] & 0xF0 | (state.temperature.clamp(16, 30) - 16);
+        // Vertical swing position, 1 is automatic swing
+        bytes[4] = bytes[4] & 0xF0 | u8::from(state.swing);
+        bytes[7] = bytes[7] & 0x0F | checksum(bytes) << 4;
+    }
+}
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Gree {
+    type Decoder = AcDecoder<Mono, Gree, NBYTES>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        AcDecoder::new(freq)
+    }
+}
+
+impl<const F: u32> ProtocolEncoder<F> for Gree {
+    type EncoderData = [u32; 6];
+    const DATA: [u32; 6] = encoder_data(&FRAME, F);
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        encode::<Self, NBYTES>(&<Self as ProtocolEncoder<F>>::DATA, cmd, b)
+    }
+}
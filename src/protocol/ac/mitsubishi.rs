@@ -0,0 +1,121 @@
+//! Mitsubishi Electric air conditioner, 144 bit frames
+//!
+//! 18 bytes, starting with `23 CB 26 01 00`, and ending with the sum of the other bytes.
+
+use crate::{
+    protocol::{
+        ac::{
+            checksum,
+            encoder::{encode, encoder_data},
+            AcCommand, AcDecoder, AcFrame, AcMode, AcProtocol, AcState, FanSpeed, Section,
+        },
+        Protocol,
+    },
+    receiver::{time::InfraMonotonic, DecoderBuilder},
+    sender::ProtocolEncoder,
+};
+
+const NBYTES: usize = 18;
+
+const FRAME: AcFrame = AcFrame {
+    header_high: 3400,
+    header_low: 1750,
+    data_high: 450,
+    zero_low: 420,
+    one_low: 1300,
+    gap: 0,
+    sections: &[Section {
+        header: true,
+        nbytes: NBYTES as u8,
+        footer_bits: 0,
+        footer: 0,
+    }],
+};
+
+const POWER: u8 = 0x20;
+const SWING: u8 = 0b0011_1000;
+
+/// Mitsubishi Electric air conditioner
+pub struct Mitsubishi;
+
+impl Protocol for Mitsubishi {
+    type Cmd = AcCommand<NBYTES>;
+}
+
+impl AcProtocol<NBYTES> for Mitsubishi {
+    const FRAME: &'static AcFrame = &FRAME;
+    #[rustfmt::skip]
+    const TEMPLATE: [u8; NBYTES] = [
+        0x23, 0xCB, 0x26, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    fn validate(bytes: &[u8; NBYTES]) -> bool {
+        bytes[..5] == Self::TEMPLATE[..5] && checksum(&bytes[..17]) == bytes[17]
+    }
+
+    fn state(bytes: &[u8; NBYTES]) -> Option<AcState> {
+        let mode = match bytes[6] & 0x38 {
+            0x08 => AcMode::Heat,
+            0x10 => AcMode::Dry,
+            0x18 => AcMode::Cool,
+            0x20 => AcMode::Auto,
+            0x38 => AcMode::Fan,
+            _ => return None,
+        };
+
+        let fan = match bytes[9] & 0x07 {
+            0 => FanSpeed::Auto,
+            1 | 2 => FanSpeed::Low,
+            3 => FanSpeed::Medium,
+            _ => FanSpeed::High,
+        };
+
+        Some(AcState {
+            power: bytes[5] & POWER != 0,
+            mode,
+            temperature: 16 + (bytes[7] & 0x0F),
+            fan,
+            swing: bytes[9] & SWING == SWING,
+        })
+    }
+
+    fn apply(state: &AcState, bytes: &mut [u8; NBYTES]) {
+        let mode = match state.mode {
+            AcMode::Heat => 0x08,
+            AcMode::Dry => 0x10,
+            AcMode::Cool => 0x18,
+            AcMode::Auto => 0x20,
+            AcMode::Fan => 0x38,
+        };
+        let fan = match state.fan {
+            FanSpeed::Auto => 0,
+            FanSpeed::Low => 1,
+            FanSpeed::Medium => 3,
+            FanSpeed::High => 5,
+        };
+
+        bytes[5] = if state.power { POWER } else { 0 };
+        bytes[6] = bytes[6] & !0x38 | mode;
+        bytes[7] = state.temperature.clamp(16, 31) - 16;
+        bytes[9] = bytes[9] & !(SWING | 0x07) | fan | if state.swing { SWING } else { 0 };
+        bytes[17] = checksum(&bytes[..17]);
+    }
+}
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Mitsubishi {
+    type Decoder = AcDecoder<Mono, Mitsubishi, NBYTES>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        AcDecoder::new(freq)
+    }
+}
+
+impl<const F: u32> ProtocolEncoder<F> for Mitsubishi {
+    type EncoderData = [u32; 6];
+    const DATA: [u32; 6] = encoder_data(&FRAME, F);
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        encode::<Self, NBYTES>(&<Self as ProtocolEncoder<F>>::DATA, cmd, b)
+    }
+}
@@ -0,0 +1,206 @@
+//! Air conditioner protocols
+//!
+//! Air conditioner remotes send the complete state of the unit, mode, temperature, fan and
+//! swing, in every frame. The frames are too long for an integer, so they are decoded into a
+//! `[u8; N]` byte array, with the bytes sent LSB first, and then into an [`AcState`].
+//!
+//! All the protocols are pulse distance coded and described by an [`AcFrame`] table. A frame is
+//! one or more sections, each ending with a stop mark, separated by a gap.
+
+use core::marker::PhantomData;
+
+use crate::{cmd::Command, protocol::Protocol};
+
+pub mod daikin;
+pub mod decoder;
+pub mod encoder;
+pub mod gree;
+pub mod mitsubishi;
+
+#[cfg(test)]
+mod tests;
+
+pub use daikin::Daikin;
+pub use decoder::{AcDecoder, AcDecoderState};
+pub use gree::Gree;
+pub use mitsubishi::Mitsubishi;
+
+/// Timing and layout of an air conditioner frame. Times in us
+#[derive(Copy, Clone, Debug)]
+pub struct AcFrame {
+    /// Header mark
+    pub header_high: u32,
+    /// Header space
+    pub header_low: u32,
+    /// Data mark
+    pub data_high: u32,
+    /// Space of a zero bit
+    pub zero_low: u32,
+    /// Space of a one bit
+    pub one_low: u32,
+    /// Space between the stop mark of a section and the next section
+    pub gap: u32,
+    /// The sections of the frame. The first section starts with a header
+    pub sections: &'static [Section],
+}
+
+/// A part of a frame
+#[derive(Copy, Clone, Debug)]
+pub struct Section {
+    /// The section starts with a header
+    pub header: bool,
+    /// Number of bytes
+    pub nbytes: u8,
+    /// Number of fixed bits sent after the bytes
+    pub footer_bits: u8,
+    /// Value of the fixed bits, LSB first
+    pub footer: u8,
+}
+
+impl AcFrame {
+    /// Number of bytes in a frame
+    pub const fn nbytes(&self) -> usize {
+        let mut n = 0;
+        let mut i = 0;
+        while i < self.sections.len() {
+            n += self.sections[i].nbytes as usize;
+            i += 1;
+        }
+        n
+    }
+
+    /// Number of entries the encoder writes to the buffer, the leading zero, the marks and the
+    /// spaces
+    pub const fn buffer_len(&self) -> usize {
+        let mut n = 1;
+        let mut i = 0;
+        while i < self.sections.len() {
+            if i > 0 {
+                n += 1;
+            }
+            if self.sections[i].header {
+                n += 2;
+            }
+            n += 2 * self.sections[i].nbits() as usize + 1;
+            i += 1;
+        }
+        n
+    }
+}
+
+impl Section {
+    /// Number of bits, including the footer
+    pub const fn nbits(&self) -> u16 {
+        self.nbytes as u16 * 8 + self.footer_bits as u16
+    }
+}
+
+/// Operating mode
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AcMode {
+    Auto,
+    Cool,
+    Heat,
+    Dry,
+    Fan,
+}
+
+/// Fan speed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FanSpeed {
+    Auto,
+    Low,
+    Medium,
+    High,
+}
+
+/// State of an air conditioner
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AcState {
+    pub power: bool,
+    pub mode: AcMode,
+    /// Temperature in degrees Celsius. Limited to the range of the protocol when encoded
+    pub temperature: u8,
+    pub fan: FanSpeed,
+    /// Vertical swing
+    pub swing: bool,
+}
+
+impl Default for AcState {
+    fn default() -> Self {
+        AcState {
+            power: true,
+            mode: AcMode::Auto,
+            temperature: 24,
+            fan: FanSpeed::Auto,
+            swing: false,
+        }
+    }
+}
+
+/// Air conditioner command. The bytes of the frame and the state decoded from them
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AcCommand<const N: usize> {
+    /// The bytes of the frame. Bytes not covered by the state are sent as they are
+    pub bytes: [u8; N],
+    /// The state. Written to the bytes, and the checksums updated, when encoded
+    pub state: AcState,
+}
+
+impl<const N: usize> Command for AcCommand<N> {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+}
+
+/// Air conditioner protocol with `N` byte frames
+pub trait AcProtocol<const N: usize>: Protocol<Cmd = AcCommand<N>> {
+    /// Timing and frame layout
+    const FRAME: &'static AcFrame;
+    /// Frame with the fixed bytes set
+    const TEMPLATE: [u8; N];
+
+    /// Check the fixed bytes and the checksums
+    fn validate(bytes: &[u8; N]) -> bool;
+
+    /// Decode the state from the bytes
+    fn state(bytes: &[u8; N]) -> Option<AcState>;
+
+    /// Write the state to the bytes and update the checksums
+    fn apply(state: &AcState, bytes: &mut [u8; N]);
+
+    /// Create a command from the template and `state`
+    fn command(state: AcState) -> AcCommand<N> {
+        let mut bytes = Self::TEMPLATE;
+        Self::apply(&state, &mut bytes);
+        AcCommand { bytes, state }
+    }
+
+    /// Unpack the received bytes into a command
+    fn unpack(bytes: &[u8; N]) -> Option<AcCommand<N>> {
+        if !Self::validate(bytes) {
+            return None;
+        }
+
+        Some(AcCommand {
+            bytes: *bytes,
+            state: Self::state(bytes)?,
+        })
+    }
+}
+
+/// Compile time check of the frame layout of `Proto` against `N`
+pub(crate) struct Layout<Proto, const N: usize>(PhantomData<Proto>);
+
+impl<Proto: AcProtocol<N>, const N: usize> Layout<Proto, N> {
+    pub(crate) const CHECK: () = assert!(Proto::FRAME.nbytes() == N, "AcFrame is not N bytes");
+}
+
+/// Sum of the bytes, modulo 256
+pub(crate) fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b))
+}
@@ -0,0 +1,200 @@
+use crate::{
+    protocol::{
+        ac::{AcCommand, AcMode, AcProtocol, AcState, Daikin, FanSpeed, Gree, Mitsubishi},
+        Protocol,
+    },
+    receiver::{BufferInputReceiver, DecoderBuilder},
+    sender::{ProtocolEncoder, PulsedataBuffer},
+};
+
+const MODES: [AcMode; 5] = [
+    AcMode::Auto,
+    AcMode::Cool,
+    AcMode::Heat,
+    AcMode::Dry,
+    AcMode::Fan,
+];
+const FANS: [FanSpeed; 4] = [
+    FanSpeed::Auto,
+    FanSpeed::Low,
+    FanSpeed::Medium,
+    FanSpeed::High,
+];
+
+fn states(temperatures: core::ops::RangeInclusive<u8>) -> impl Iterator<Item = AcState> {
+    temperatures.flat_map(|temperature| {
+        MODES.into_iter().flat_map(move |mode| {
+            FANS.into_iter().map(move |fan| AcState {
+                power: temperature % 2 == 0,
+                mode,
+                temperature,
+                fan,
+                swing: temperature % 3 == 0,
+            })
+        })
+    })
+}
+
+fn roundtrip<P, const N: usize, const F: u32>(state: AcState) -> Option<AcCommand<N>>
+where
+    P: AcProtocol<N> + ProtocolEncoder<F> + DecoderBuilder<u32>,
+    P: Protocol<Cmd = AcCommand<N>>,
+{
+    let mut ptb = PulsedataBuffer::<512>::new();
+    ptb.load::<P, F>(&P::command(state));
+
+    let mut brecv = BufferInputReceiver::<P>::with_frequenzy(F);
+    let cmd = brecv.iter(ptb.buffer()).next();
+    cmd
+}
+
+#[test]
+fn mitsubishi() {
+    for state in states(16..=31) {
+        let cmd = roundtrip::<Mitsubishi, 18, 40_000>(state).unwrap();
+        assert_eq!(cmd.state, state);
+        assert_eq!(cmd, Mitsubishi::command(state));
+    }
+
+    let cmd = Mitsubishi::command(AcState::default());
+    assert_eq!(
+        &cmd.bytes[..8],
+        &[0x23, 0xCB, 0x26, 0x01, 0x00, 0x20, 0x20, 0x08]
+    );
+    assert_eq!(cmd.bytes[17], 0x5D);
+}
+
+#[test]
+fn daikin() {
+    for state in states(10..=32) {
+        let cmd = roundtrip::<Daikin, 27, 40_000>(state).unwrap();
+        assert_eq!(cmd.state, state);
+    }
+
+    let cmd = Daikin::command(AcState::default());
+    assert_eq!(cmd.bytes[7], 0x02);
+    assert_eq!(&cmd.bytes[13..17], &[0x01, 48, 0x00, 0xA0]);
+}
+
+#[test]
+fn gree() {
+    for state in states(16..=30) {
+        let cmd = roundtrip::<Gree, 8, 40_000>(state).unwrap();
+        assert_eq!(cmd.state, state);
+    }
+
+    let state = AcState {
+        power: true,
+        mode: AcMode::Cool,
+        temperature: 24,
+        fan: FanSpeed::Auto,
+        swing: false,
+    };
+    let cmd = Gree::command(state);
+    assert_eq!(cmd.bytes, [0x09, 0x08, 0x20, 0x50, 0x00, 0x20, 0x00, 0xD0]);
+}
+
+#[test]
+fn temperature_is_clamped() {
+    let state = AcState {
+        temperature: 40,
+        ..AcState::default()
+    };
+
+    let cmd = roundtrip::<Gree, 8, 40_000>(state).unwrap();
+    assert_eq!(cmd.state.temperature, 30);
+
+    let cmd = roundtrip::<Mitsubishi, 18, 40_000>(state).unwrap();
+    assert_eq!(cmd.state.temperature, 31);
+}
+
+#[test]
+fn unknown_bytes_are_kept() {
+    let mut cmd = Daikin::command(AcState::default());
+    cmd.bytes[20] = 0x5A;
+    cmd.state.mode = AcMode::Heat;
+
+    let mut ptb = PulsedataBuffer::<512>::new();
+    ptb.load::<Daikin, 1_000_000>(&cmd);
+
+    let mut brecv = BufferInputReceiver::<Daikin>::with_frequenzy(1_000_000);
+    let res = brecv.iter(ptb.buffer()).next().unwrap();
+
+    assert_eq!(res.bytes[20], 0x5A);
+    assert_eq!(res.state.mode, AcMode::Heat);
+}
+
+#[test]
+fn bad_checksum() {
+    let mut bytes = Mitsubishi::command(AcState::default()).bytes;
+    assert!(Mitsubishi::unpack(&bytes).is_some());
+    bytes[10] ^= 0x01;
+    assert_eq!(Mitsubishi::unpack(&bytes), None);
+
+    let mut bytes = Daikin::command(AcState::default()).bytes;
+    bytes[2] ^= 0x01;
+    assert_eq!(Daikin::unpack(&bytes), None);
+
+    let mut bytes = Gree::command(AcState::default()).bytes;
+    bytes[1] ^= 0x01;
+    assert_eq!(Gree::unpack(&bytes), None);
+}
+
+#[test]
+fn gree_footer() {
+    let mut ptb = PulsedataBuffer::<512>::new();
+    ptb.load::<Gree, 1_000_000>(&Gree::command(AcState::default()));
+
+    // Flip the middle footer bit, after the header and the 32 bits of the first section
+    let mut buf = std::vec::Vec::from(ptb.buffer());
+    let footer = 3 + 2 * 32 + 3;
+    buf[footer] = if buf[footer] == 540 { 1600 } else { 540 };
+
+    let mut brecv = BufferInputReceiver::<Gree>::with_frequenzy(1_000_000);
+    assert_eq!(brecv.iter(&buf).next(), None);
+}
+
+#[test]
+fn buffer_len() {
+    let cmd = Daikin::command(AcState::default());
+    let len = Daikin::FRAME.buffer_len();
+
+    let mut buf = [0; 512];
+    assert_eq!(
+        <Daikin as ProtocolEncoder<1_000_000>>::encode(&cmd, &mut buf),
+        len
+    );
+
+    // Too short, nothing is encoded
+    assert_eq!(
+        <Daikin as ProtocolEncoder<1_000_000>>::encode(&cmd, &mut buf[..len - 1]),
+        0
+    );
+    let mut ptb = PulsedataBuffer::<128>::new();
+    ptb.load::<Daikin, 1_000_000>(&cmd);
+    assert!(ptb.buffer().is_empty());
+}
+
+#[test]
+fn clock_frequencies() {
+    let state = AcState {
+        mode: AcMode::Heat,
+        temperature: 21,
+        fan: FanSpeed::High,
+        ..AcState::default()
+    };
+
+    for cmd in [
+        roundtrip::<Gree, 8, 20_000>(state),
+        roundtrip::<Gree, 8, 48_000_000>(state),
+    ] {
+        assert_eq!(cmd.map(|c| c.state), Some(state));
+    }
+
+    for cmd in [
+        roundtrip::<Daikin, 27, 20_000>(state),
+        roundtrip::<Daikin, 27, 48_000_000>(state),
+    ] {
+        assert_eq!(cmd.map(|c| c.state), Some(state));
+    }
+}
@@ -0,0 +1,227 @@
+use core::marker::PhantomData;
+
+use crate::{
+    protocol::biphase::{BiPhase, BiPhaseProtocol, Level},
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecodingError, ProtocolDecoder, State,
+    },
+};
+
+/// Tolerance of 1 to 8 units
+const TOL: [u32; 8] = [25, 20, 15, 12, 10, 8, 7, 6];
+
+/// Decoder for protocols described by a [`BiPhase`] table
+pub struct BiPhaseDecoder<Mono: InfraMonotonic, Proto> {
+    pub(crate) state: BiPhaseState,
+    /// Time since the start of the header, in half units
+    t: u16,
+    /// Start of the next bit, in half units
+    bit_start: u16,
+    /// Length of the frame
+    nbits: u8,
+    /// Received bits, the first bit as the most significant
+    bits: u64,
+    proto: PhantomData<Proto>,
+    spans: PulseSpans<Mono>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BiPhaseState {
+    Idle,
+    /// Number of bits received
+    Data(u8),
+    /// Frame received, with length
+    Done(u8),
+    Err(DecodingError),
+}
+
+impl From<BiPhaseState> for State {
+    fn from(state: BiPhaseState) -> Self {
+        match state {
+            BiPhaseState::Idle => State::Idle,
+            BiPhaseState::Data(_) => State::Receiving,
+            BiPhaseState::Done(_) => State::Done,
+            BiPhaseState::Err(err) => State::Error(err),
+        }
+    }
+}
+
+/// Length of the spaces before the first mark of the header, in half units
+const fn leading_space(pd: &BiPhase) -> u16 {
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < pd.header.len() {
+        match pd.header[i] {
+            Level::Space(units) => len += 2 * units as u16,
+            Level::Mark(_) => break,
+        }
+        i += 1;
+    }
+
+    len
+}
+
+/// Length of the header, in half units
+const fn header_len(pd: &BiPhase) -> u16 {
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < pd.header.len() {
+        match pd.header[i] {
+            Level::Space(units) | Level::Mark(units) => len += 2 * units as u16,
+        }
+        i += 1;
+    }
+
+    len
+}
+
+impl<Mono: InfraMonotonic, Proto: BiPhaseProtocol> BiPhaseDecoder<Mono, Proto> {
+    /// Create a decoder for a sample rate of `freq`
+    pub fn new(freq: u32) -> Self {
+        let unit = Proto::DESCRIPTOR.unit;
+        let pulses = [
+            unit,
+            2 * unit,
+            3 * unit,
+            4 * unit,
+            5 * unit,
+            6 * unit,
+            7 * unit,
+            8 * unit,
+        ];
+
+        BiPhaseDecoder {
+            state: BiPhaseState::Idle,
+            t: 0,
+            bit_start: 0,
+            nbits: 0,
+            bits: 0,
+            proto: PhantomData,
+            spans: PulseSpans::new(freq, &pulses, &TOL),
+        }
+    }
+
+    /// Start a new frame at time `t`
+    fn start(&mut self, t: u16) {
+        let pd = Proto::DESCRIPTOR;
+
+        self.t = t;
+        self.bit_start = header_len(pd);
+        self.bits = 0;
+        self.nbits = Proto::nbits(0, 0);
+    }
+
+    /// A mark or a space of `units` units, after `n` received bits
+    ///
+    /// Levels not matching the header or the bit timing, as from other bi-phase protocols,
+    /// make the decoder go back to idle.
+    fn level(&mut self, mark: bool, units: u16, mut n: u8) -> BiPhaseState {
+        let pd = Proto::DESCRIPTOR;
+        let end = self.t + 2 * units;
+
+        // Check the overlapped parts of the header
+        let mut start = 0;
+        for level in pd.header {
+            let (is_mark, len) = match *level {
+                Level::Mark(len) => (true, len),
+                Level::Space(len) => (false, len),
+            };
+            let stop = start + 2 * u16::from(len);
+
+            if start < end && self.t < stop && is_mark != mark {
+                return BiPhaseState::Idle;
+            }
+            start = stop;
+        }
+
+        // Sample the bits with the middle of the first half in this level
+        while n < self.nbits {
+            let w = pd.width(n);
+
+            if self.bit_start + w >= end {
+                break;
+            }
+
+            if self.bit_start + 3 * w < end {
+                // No transition in the middle of the bit, not a frame of this protocol
+                return BiPhaseState::Idle;
+            }
+
+            let one = mark == pd.mark_first(true);
+            self.bits = (self.bits << 1) | u64::from(one);
+            self.bit_start += 4 * w;
+            n += 1;
+            self.nbits = Proto::nbits(self.bits, n);
+        }
+
+        self.t = end;
+
+        if n >= self.nbits {
+            BiPhaseState::Done(n)
+        } else {
+            BiPhaseState::Data(n)
+        }
+    }
+}
+
+impl<Mono, Proto> ProtocolDecoder<Proto, Mono> for BiPhaseDecoder<Mono, Proto>
+where
+    Mono: InfraMonotonic,
+    Proto: BiPhaseProtocol,
+{
+    #[rustfmt::skip]
+    fn event(&mut self, rising: bool, dt: Mono::Duration) -> State {
+        use BiPhaseState::*;
+
+        let pd = Proto::DESCRIPTOR;
+
+        // Length of the level in units
+        let units = self.spans.get::<usize>(dt).map(|v| v as u16 + 1);
+        // On the rising edge a space ended
+        let mark = !rising;
+
+        self.state = match (self.state, units) {
+            // The first mark of the header
+            (Idle,      _) if rising        => { self.start(leading_space(pd)); Data(0) }
+            // The start of the header was used to end the previous frame
+            (Idle,      Some(units)) if pd.header.first() == Some(&Level::Mark(units as u8)) => {
+                self.start(0);
+                self.level(mark, units, 0)
+            }
+            (Idle,      _)                  => Idle,
+
+            (Data(n),   Some(units))        => self.level(mark, units, n),
+            (Data(n),   None) if Proto::ends_early(self.bits, n) => Done(n),
+            // A pause, the start of a new frame
+            (Data(_),   None) if rising     => { self.start(leading_space(pd)); Data(0) }
+            (Data(_),   None)               => Err(DecodingError::Data),
+
+            (Done(n),   _)                  => Done(n),
+            (Err(err),  _)                  => Err(err),
+        };
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<Proto::Cmd> {
+        match self.state {
+            BiPhaseState::Done(nbits) => Proto::unpack(self.bits, nbits),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = BiPhaseState::Idle;
+        self.t = 0;
+        self.bit_start = 0;
+        self.bits = 0;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.spans
+    }
+}
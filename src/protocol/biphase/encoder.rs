@@ -0,0 +1,69 @@
+use crate::protocol::{
+    biphase::{BiPhase, BiPhaseProtocol, Level},
+    utils::scale_with_samplerate,
+};
+
+/// Length of the unit in ticks of a `freq` Hz clock, used as `ProtocolEncoder::DATA`
+pub const fn encoder_data(pd: &BiPhase, freq: u32) -> [u32; 1] {
+    [scale_with_samplerate(pd.unit, freq)]
+}
+
+/// Encode `cmd` into `b`, with the unit length from [`encoder_data`]
+pub fn encode<Proto: BiPhaseProtocol>(data: &[u32; 1], cmd: &Proto::Cmd, b: &mut [u32]) -> usize {
+    let pd = Proto::DESCRIPTOR;
+    let unit = data[0];
+    let (bits, nbits) = Proto::pack(cmd);
+
+    let mut levels = Levels { b, index: 0 };
+    levels.b[0] = 0;
+
+    for level in pd.header {
+        match *level {
+            Level::Mark(units) => levels.push(true, u32::from(units) * unit),
+            Level::Space(units) => levels.push(false, u32::from(units) * unit),
+        }
+    }
+
+    for n in 0..nbits {
+        let one = (bits >> (nbits - 1 - n)) & 1 != 0;
+        let len = u32::from(pd.width(n)) * unit;
+        let mark = pd.mark_first(one);
+
+        levels.push(mark, len);
+        levels.push(!mark, len);
+    }
+
+    levels.len()
+}
+
+/// Merges the levels into the buffer
+struct Levels<'a> {
+    b: &'a mut [u32],
+    /// Index of the current level. Even indices are spaces and odd are marks
+    index: usize,
+}
+
+impl Levels<'_> {
+    fn push(&mut self, mark: bool, len: u32) {
+        if !mark && self.index == 0 {
+            // Spaces before the first mark are not sent
+            return;
+        }
+
+        if mark == (self.index % 2 == 1) {
+            self.b[self.index] += len;
+        } else {
+            self.index += 1;
+            self.b[self.index] = len;
+        }
+    }
+
+    /// Length of the buffer, without the trailing space
+    fn len(&self) -> usize {
+        if self.index % 2 == 1 {
+            self.index + 1
+        } else {
+            self.index
+        }
+    }
+}
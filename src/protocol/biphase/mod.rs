@@ -0,0 +1,145 @@
+//! Generic bi-phase (Manchester) protocol
+//!
+//! Decoder and encoder for protocols where every bit is a mark and a space of equal length, and
+//! the value of the bit is given by the order of them, as in Philips Rc5 and Rc6.
+//!
+//! A protocol is described by a [`BiPhase`] table, with the unit time, the header and the
+//! frame layout, and implements [`BiPhaseProtocol`] to unpack and pack the bits. The bits are
+//! given to the protocol with the first transmitted bit as the most significant bit.
+//!
+//! ```
+//! use infrared::{
+//!     protocol::{
+//!         biphase::{
+//!             encoder::{encode, encoder_data},
+//!             BiPhase, BiPhaseDecoder, BiPhaseProtocol, Level, Polarity,
+//!         },
+//!         Protocol,
+//!     },
+//!     receiver::{time::InfraMonotonic, DecoderBuilder},
+//!     sender::ProtocolEncoder,
+//! };
+//!
+//! /// 12 bit protocol with a start bit and a double width toggle bit
+//! pub struct MyProtocol;
+//!
+//! impl Protocol for MyProtocol {
+//!     type Cmd = u16;
+//! }
+//!
+//! impl BiPhaseProtocol for MyProtocol {
+//!     const DESCRIPTOR: &'static BiPhase = &BiPhase {
+//!         unit: 500,
+//!         header: &[Level::Mark(4), Level::Space(2), Level::Mark(1), Level::Space(1)],
+//!         nbits: 12,
+//!         polarity: Polarity::MarkSpace,
+//!         double_width: 1 << 0,
+//!     };
+//!
+//!     fn unpack(bits: u64, _nbits: u8) -> Option<u16> {
+//!         Some(bits as u16)
+//!     }
+//!
+//!     fn pack(cmd: &u16) -> (u64, u8) {
+//!         (u64::from(*cmd & 0xFFF), 12)
+//!     }
+//! }
+//!
+//! impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for MyProtocol {
+//!     type Decoder = BiPhaseDecoder<Mono, MyProtocol>;
+//!
+//!     fn build(freq: u32) -> Self::Decoder {
+//!         BiPhaseDecoder::new(freq)
+//!     }
+//! }
+//!
+//! impl<const F: u32> ProtocolEncoder<F> for MyProtocol {
+//!     type EncoderData = [u32; 1];
+//!     const DATA: [u32; 1] = encoder_data(MyProtocol::DESCRIPTOR, F);
+//!
+//!     fn encode(cmd: &u16, b: &mut [u32]) -> usize {
+//!         encode::<Self>(&<Self as ProtocolEncoder<F>>::DATA, cmd, b)
+//!     }
+//! }
+//! ```
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+pub use decoder::{BiPhaseDecoder, BiPhaseState};
+
+use crate::protocol::Protocol;
+
+/// Timing and frame layout of a bi-phase protocol
+#[derive(Copy, Clone, Debug)]
+pub struct BiPhase {
+    /// Length of a half bit, in us
+    pub unit: u32,
+    /// Fixed pattern sent before the bits, in units. Leading spaces are the part of the
+    /// pattern before the first mark. A run of equal levels can be at most 8 units long
+    pub header: &'static [Level],
+    /// Number of bits in a frame, at most 64
+    pub nbits: u8,
+    /// Order of the mark and the space in a one
+    pub polarity: Polarity,
+    /// Mask of the bits sent with double width, bit 0 is the first transmitted bit
+    pub double_width: u64,
+}
+
+/// A mark or a space of a number of units
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Level {
+    Mark(u8),
+    Space(u8),
+}
+
+/// Order of the mark and space in a one
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    /// A one is a mark followed by a space, as in Rc6
+    MarkSpace,
+    /// A one is a space followed by a mark, as in Rc5
+    SpaceMark,
+}
+
+impl BiPhase {
+    /// Width of a half of the `n`:th bit, in units
+    pub const fn width(&self, n: u8) -> u16 {
+        if n < 64 && self.double_width & (1 << n) != 0 {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Is the first half of a bit with value `one` a mark
+    pub const fn mark_first(&self, one: bool) -> bool {
+        one == matches!(self.polarity, Polarity::MarkSpace)
+    }
+}
+
+/// Protocol described by a bi-phase table
+pub trait BiPhaseProtocol: Protocol {
+    /// Timing and frame layout
+    const DESCRIPTOR: &'static BiPhase;
+
+    /// Length of the frame, when the first `n` bits have been received. For protocols with
+    /// the length coded in the frame
+    fn nbits(_bits: u64, _n: u8) -> u8 {
+        Self::DESCRIPTOR.nbits
+    }
+
+    /// Can a frame end after `n` bits, followed by a pause
+    fn ends_early(_bits: u64, _n: u8) -> bool {
+        false
+    }
+
+    /// Unpack the `nbits` received bits into a command
+    fn unpack(bits: u64, nbits: u8) -> Option<Self::Cmd>;
+
+    /// Pack the command into bits, and the number of bits
+    fn pack(cmd: &Self::Cmd) -> (u64, u8);
+}
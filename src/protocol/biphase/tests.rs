@@ -0,0 +1,120 @@
+use crate::{
+    protocol::{
+        biphase::{
+            encoder::{encode, encoder_data},
+            BiPhase, BiPhaseDecoder, BiPhaseProtocol, Level, Polarity,
+        },
+        Protocol,
+    },
+    receiver::{time::InfraMonotonic, BufferInputReceiver, DecoderBuilder},
+    sender::{ProtocolEncoder, PulsedataBuffer},
+};
+
+/// 12 bits with a header and the first bit double width
+struct Header12;
+
+impl Protocol for Header12 {
+    type Cmd = u16;
+}
+
+impl BiPhaseProtocol for Header12 {
+    const DESCRIPTOR: &'static BiPhase = &BiPhase {
+        unit: 500,
+        header: &[
+            Level::Mark(4),
+            Level::Space(2),
+            Level::Mark(1),
+            Level::Space(1),
+        ],
+        nbits: 12,
+        polarity: Polarity::MarkSpace,
+        double_width: 1 << 0,
+    };
+
+    fn unpack(bits: u64, _nbits: u8) -> Option<u16> {
+        Some(bits as u16)
+    }
+
+    fn pack(cmd: &u16) -> (u64, u8) {
+        (u64::from(*cmd & 0xFFF), 12)
+    }
+}
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Header12 {
+    type Decoder = BiPhaseDecoder<Mono, Header12>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        BiPhaseDecoder::new(freq)
+    }
+}
+
+impl<const F: u32> ProtocolEncoder<F> for Header12 {
+    type EncoderData = [u32; 1];
+    const DATA: [u32; 1] = encoder_data(Header12::DESCRIPTOR, F);
+
+    fn encode(cmd: &u16, b: &mut [u32]) -> usize {
+        encode::<Self>(&<Self as ProtocolEncoder<F>>::DATA, cmd, b)
+    }
+}
+
+#[test]
+fn encode_levels() {
+    const FREQ: u32 = 1_000_000;
+    let mut ptb = PulsedataBuffer::<64>::new();
+
+    ptb.load::<Header12, FREQ>(&0b1000_0000_0001);
+
+    #[rustfmt::skip]
+    let expected = [
+        // Header
+        0, 2000, 1000, 500, 500,
+        // The double width one, the space merged with the space of the first zero
+        1000, 1000 + 500,
+        // Zeros
+        500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 500,
+        // The mark of the last one merged with the mark of the last zero
+        1000,
+    ];
+
+    assert_eq!(ptb.buffer(), &expected);
+}
+
+#[test]
+fn all_commands() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<64>::new();
+
+    for cmd in 0..0x1000 {
+        ptb.reset();
+        ptb.load::<Header12, FREQ>(&cmd);
+
+        let mut brecv = BufferInputReceiver::<Header12>::with_frequenzy(FREQ);
+        assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+    }
+}
+
+#[test]
+fn missing_transition() {
+    const FREQ: u32 = 1_000_000;
+
+    // The zero after the double width bit is a mark for a full bit
+    let buf = [0, 2000, 1000, 500, 500, 1000, 1000 + 500, 1500, 500];
+
+    let mut brecv = BufferInputReceiver::<Header12>::with_frequenzy(FREQ);
+    assert_eq!(brecv.iter(&buf).next(), None);
+}
+
+#[test]
+fn bad_pulse() {
+    use crate::receiver::{DecodingError, ProtocolDecoder, State};
+
+    // A mark of 2.5 units in the data
+    let buf = [0, 2000, 1000, 500, 500, 1000, 1500, 1250];
+
+    let mut decoder = <Header12 as DecoderBuilder<u32>>::build(1_000_000);
+    let mut state = State::Idle;
+    for (i, dt) in buf.iter().enumerate() {
+        state = decoder.event(i & 0x1 == 0, *dt);
+    }
+    assert_eq!(state, State::Error(DecodingError::Data));
+}
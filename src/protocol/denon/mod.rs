@@ -1,10 +1,22 @@
+//! Denon
+//!
+//! Denon variant of the Kaseikyo 48 bit protocol. After the vendor id (0x3254) and vendor
+//! parity nibble, the frame contains the 4 bit device, 8 bit sub-device and 8 bit command,
+//! followed by a 8 bit checksum.
+//!
+//! The fields are the same as in a `KaseikyoCommand` with the Denon vendor, see
+//! `protocol::kaseikyo` for the layout.
+
+use core::convert::TryInto;
+
 use crate::{
-    cmd::AnyCommand,
-    protocol::Protocol,
+    cmd::{AddressCommand, Command},
+    protocol::{utils::scale_with_samplerate, Protocol},
     receiver::{
         time::{InfraMonotonic, PulseSpans},
-        DecoderBuilder, ProtocolDecoder, State,
+        DecoderBuilder, DecodingError, ProtocolDecoder, State,
     },
+    sender::ProtocolEncoder,
     ProtocolId,
 };
 
@@ -29,6 +41,9 @@ const PULSE: [u32; 8] = [
 
 const TOL: [u32; 8] = [8, 10, 10, 0, 0, 0, 0, 0];
 
+/// Vendor id and vendor parity
+const DENON_VENDOR: u64 = 0x0_3254;
+
 /// Denon protocol
 pub struct Denon;
 
@@ -56,22 +71,77 @@ pub struct DenonDecoder<Mono: InfraMonotonic> {
     spans: PulseSpans<Mono>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DenonCommand {
-    pub bits: u64,
+    /// Device. 4 bits
+    pub device: u8,
+    /// Sub-device. 8 bits
+    pub subdevice: u8,
+    /// Command. 8 bits
+    pub cmd: u8,
 }
 
-impl From<DenonCommand> for AnyCommand {
-    fn from(cmd: DenonCommand) -> AnyCommand {
-        AnyCommand {
-            protocol: ProtocolId::Denon,
-            raw: cmd.bits,
-            address: 0,
-            command: 0,
-            repeat: false,
+impl DenonCommand {
+    pub const fn new(device: u8, subdevice: u8, cmd: u8) -> Self {
+        Self {
+            device,
+            subdevice,
+            cmd,
         }
     }
+
+    pub const fn unpack(bits: u64) -> Self {
+        Self {
+            device: ((bits >> 20) & 0xF) as u8,
+            subdevice: ((bits >> 24) & 0xFF) as u8,
+            cmd: ((bits >> 32) & 0xFF) as u8,
+        }
+    }
+
+    pub fn pack(&self) -> u64 {
+        let data = u64::from(self.device & 0xF) << 20
+            | u64::from(self.subdevice) << 24
+            | u64::from(self.cmd) << 32;
+
+        // Xor of byte 2, 3 and 4
+        let checksum = ((data >> 16) ^ (data >> 24) ^ (data >> 32)) & 0xFF;
+
+        DENON_VENDOR | data | checksum << 40
+    }
+}
+
+impl Command for DenonCommand {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+}
+
+impl AddressCommand for DenonCommand {
+    const ID: ProtocolId = ProtocolId::Denon;
+
+    fn raw(&self) -> u64 {
+        self.pack()
+    }
+
+    /// Device in bit 8 - 11 and sub-device in bit 0 - 7
+    fn address(&self) -> u32 {
+        u32::from(self.device) << 8 | u32::from(self.subdevice)
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        let addr: u16 = addr.try_into().ok().filter(|addr| *addr <= 0xFFF)?;
+
+        Some(DenonCommand::new(
+            (addr >> 8) as u8,
+            addr as u8,
+            cmd.try_into().ok()?,
+        ))
+    }
 }
 
 impl<Mono: InfraMonotonic> ProtocolDecoder<Denon, Mono> for DenonDecoder<Mono> {
@@ -85,12 +155,13 @@ impl<Mono: InfraMonotonic> ProtocolDecoder<Denon, Mono> for DenonDecoder<Mono> {
             self.state = match (self.state, pulsewidth) {
                 (DenonState::Idle,          PulseWidth::Sync)   => DenonState::Data(0),
                 (DenonState::Idle,          _)                  => DenonState::Idle,
-                (DenonState::Data(47),      PulseWidth::Zero)   => DenonState::Done,
-                (DenonState::Data(47),      PulseWidth::One)    => DenonState::Done,
+                (DenonState::Data(47),      PulseWidth::Zero)   => self.validate(),
+                (DenonState::Data(47),      PulseWidth::One)    => { self.buf |= 1 << 47; self.validate() }
                 (DenonState::Data(idx),     PulseWidth::Zero)   => DenonState::Data(idx + 1),
                 (DenonState::Data(idx),     PulseWidth::One)    => { self.buf |= 1 << idx; DenonState::Data(idx + 1) }
-                (DenonState::Data(_ix),     _)                  => DenonState::Idle,
+                (DenonState::Data(_ix),     _)                  => DenonState::Err(DecodingError::Data),
                 (DenonState::Done,          _)                  => DenonState::Done,
+                (DenonState::Err(err),      _)                  => DenonState::Err(err),
             };
 
             self.dt_save = Mono::ZERO_DURATION;
@@ -103,7 +174,7 @@ impl<Mono: InfraMonotonic> ProtocolDecoder<Denon, Mono> for DenonDecoder<Mono> {
 
     fn command(&self) -> Option<DenonCommand> {
         if self.state == DenonState::Done {
-            Some(DenonCommand { bits: self.buf })
+            Some(DenonCommand::unpack(self.buf))
         } else {
             None
         }
@@ -119,12 +190,65 @@ impl<Mono: InfraMonotonic> ProtocolDecoder<Denon, Mono> for DenonDecoder<Mono> {
     }
 }
 
+impl<Mono: InfraMonotonic> DenonDecoder<Mono> {
+    /// Check the checksum, and the vendor id and vendor parity
+    fn validate(&self) -> DenonState {
+        let buf = self.buf;
+        let checksum = ((buf >> 16) ^ (buf >> 24) ^ (buf >> 32)) & 0xFF;
+
+        if checksum == buf >> 40 && buf & 0xF_FFFF == DENON_VENDOR {
+            DenonState::Done
+        } else {
+            DenonState::Err(DecodingError::Validation)
+        }
+    }
+}
+
+impl<const F: u32> ProtocolEncoder<F> for Denon {
+    type EncoderData = [u32; 5];
+    const DATA: Self::EncoderData = [
+        scale_with_samplerate(HEADER_HIGH, F),
+        scale_with_samplerate(HEADER_LOW, F),
+        scale_with_samplerate(DATA_HIGH, F),
+        scale_with_samplerate(ZERO_LOW, F),
+        scale_with_samplerate(ONE_LOW, F),
+    ];
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        let [header_high, header_low, data_high, zero_low, one_low] =
+            <Self as ProtocolEncoder<F>>::DATA;
+
+        b[0] = 0;
+        b[1] = header_high;
+        b[2] = header_low;
+
+        let bits = cmd.pack();
+        let mut bi = 3;
+
+        for i in 0..48 {
+            b[bi] = data_high;
+            b[bi + 1] = if (bits >> i) & 1 != 0 {
+                one_low
+            } else {
+                zero_low
+            };
+            bi += 2;
+        }
+
+        // Stop bit
+        b[bi] = data_high;
+
+        bi + 1
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum DenonState {
     Idle,
     Data(u8),
     Done,
+    Err(DecodingError),
 }
 
 impl From<DenonState> for State {
@@ -133,6 +257,7 @@ impl From<DenonState> for State {
             DenonState::Idle => State::Idle,
             DenonState::Data(_) => State::Receiving,
             DenonState::Done => State::Done,
+            DenonState::Err(err) => State::Error(err),
         }
     }
 }
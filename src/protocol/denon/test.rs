@@ -1,4 +1,14 @@
-use crate::{protocol::Denon, receiver::BufferInputReceiver};
+use crate::{
+    cmd::AddressCommand,
+    protocol::{
+        denon::DenonCommand,
+        kaseikyo::{KaseikyoCommand, Vendor},
+        Denon,
+    },
+    receiver::{BufferInputReceiver, DecodingError},
+    sender::PulsedataBuffer,
+    Receiver,
+};
 
 #[test]
 fn denon() {
@@ -22,9 +32,109 @@ fn denon() {
     let mut brecv = BufferInputReceiver::<Denon>::with_frequenzy(40_000);
     let cmds = brecv.iter(dists).collect::<Vec<_>>();
 
-    assert_eq!(cmds.len(), 2);
+    // Bit 12 of the vendor id is lost in the second frame, it doesn't validate
+    assert_eq!(cmds.len(), 1);
 
     for cmd in &cmds {
-        println!("{:?}", cmd);
+        assert_eq!(cmd, &DenonCommand::new(4, 1, 0x01));
+        assert_eq!(cmd.address(), 0x401);
+        assert_eq!(cmd.command(), 0x01);
+    }
+}
+
+#[test]
+fn cmd_pack_unpack() {
+    let cmd = DenonCommand::new(4, 1, 0x01);
+
+    assert_eq!(cmd.pack(), 0x4001_0140_3254);
+    // Same fields as a Kaseikyo frame with the Denon vendor
+    assert_eq!(
+        cmd.pack(),
+        KaseikyoCommand::new(Vendor::Denon, 4, 1, 0x01).pack()
+    );
+    assert_eq!(DenonCommand::unpack(cmd.pack()), cmd);
+
+    let cmd = DenonCommand::create(0xA5B, 0xED).unwrap();
+    assert_eq!(cmd, DenonCommand::new(0xA, 0x5B, 0xED));
+    assert_eq!(DenonCommand::unpack(cmd.pack()), cmd);
+
+    assert_eq!(DenonCommand::create(0x1000, 0xED), None);
+    assert_eq!(DenonCommand::create(0xA5B, 0x100), None);
+}
+
+#[test]
+fn all_commands() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+
+    for address in (0..0x1000).step_by(15) {
+        for cmdnum in 0..=255 {
+            ptb.reset();
+            let cmd = DenonCommand::create(address, cmdnum).unwrap();
+            ptb.load::<Denon, FREQ>(&cmd);
+
+            let mut brecv = BufferInputReceiver::<Denon>::with_frequenzy(FREQ);
+            let cmdres = brecv.iter(ptb.buffer()).next();
+
+            assert_eq!(cmdres, Some(cmd));
+        }
+    }
+}
+
+#[test]
+fn malformed_pulse() {
+    let mut ptb = PulsedataBuffer::<128>::new();
+    ptb.load::<Denon, 1_000_000>(&DenonCommand::new(4, 1, 0x01));
+
+    // Stretch a space in the middle of the frame
+    let mut buf = std::vec::Vec::from(ptb.buffer());
+    buf[40] = 3000;
+
+    let mut recv = Receiver::<Denon>::new(1_000_000);
+    let mut edge = false;
+    let mut res = Ok(None);
+
+    for dt in buf {
+        edge = !edge;
+        res = recv.event(dt, edge);
+        if res.is_err() {
+            break;
+        }
+    }
+
+    assert_eq!(res, Err(DecodingError::Data));
+}
+
+#[test]
+fn validation() {
+    let bits = DenonCommand::new(4, 1, 0x01).pack();
+
+    // The frame, one bit flipped in the vendor id, and one in the data
+    let frames = [
+        (bits, Ok(Some(DenonCommand::new(4, 1, 0x01)))),
+        (bits ^ 1 << 12, Err(DecodingError::Validation)),
+        (bits ^ 1 << 30, Err(DecodingError::Validation)),
+    ];
+
+    for (bits, expected) in frames {
+        let mut recv = Receiver::<Denon>::new(1_000_000);
+        let mut res = Ok(None);
+
+        let pulses = [3400, 1600];
+        let data = (0..48).flat_map(|i| [480, if bits >> i & 1 != 0 { 1200 } else { 360 }]);
+
+        for (i, dt) in core::iter::once(0)
+            .chain(pulses)
+            .chain(data)
+            .chain([480])
+            .enumerate()
+        {
+            res = recv.event(dt, i & 0x1 == 0);
+            if res != Ok(None) {
+                break;
+            }
+        }
+
+        assert_eq!(res, expected);
     }
 }
@@ -0,0 +1,17 @@
+//! Grundig decoder. Grundig is decoded by the generic bi-phase decoder
+
+use crate::{
+    protocol::{biphase::BiPhaseDecoder, Grundig},
+    receiver::{time::InfraMonotonic, DecoderBuilder},
+};
+
+/// Grundig decoder
+pub type GrundigDecoder<Mono> = BiPhaseDecoder<Mono, Grundig>;
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Grundig {
+    type Decoder = GrundigDecoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        BiPhaseDecoder::new(freq)
+    }
+}
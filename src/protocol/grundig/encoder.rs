@@ -0,0 +1,16 @@
+use crate::{
+    protocol::{
+        biphase::encoder::{encode, encoder_data},
+        Grundig,
+    },
+    sender::ProtocolEncoder,
+};
+
+impl<const FREQ: u32> ProtocolEncoder<FREQ> for Grundig {
+    type EncoderData = [u32; 1];
+    const DATA: Self::EncoderData = encoder_data(&super::GRUNDIG_BIPHASE, FREQ);
+
+    fn encode(cmd: &Self::Cmd, buf: &mut [u32]) -> usize {
+        encode::<Self>(&<Self as ProtocolEncoder<FREQ>>::DATA, cmd, buf)
+    }
+}
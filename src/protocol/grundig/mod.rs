@@ -0,0 +1,116 @@
+//! Grundig
+//!
+//! Bi-phase coded protocol with a 528 us unit. A frame is a 528 us pre-pulse, a 2.6 ms pause
+//! and a start bit, always one, followed by the 9 bit command, LSB first. A one is a mark
+//! followed by a space. The protocol has no address.
+//!
+//! An info frame, with all command bits set, is sent before the first and after the last
+//! frame of a button press.
+
+use core::convert::TryInto;
+
+pub mod decoder;
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::{
+        biphase::{BiPhase, BiPhaseProtocol, Level, Polarity},
+        Protocol,
+    },
+    ProtocolId,
+};
+
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+const NBITS: u8 = 9;
+
+/// Pre-pulse, pause and the start bit
+const GRUNDIG_BIPHASE: BiPhase = BiPhase {
+    unit: 528,
+    header: &[
+        Level::Mark(1),
+        Level::Space(5),
+        Level::Mark(1),
+        Level::Space(1),
+    ],
+    nbits: NBITS,
+    polarity: Polarity::MarkSpace,
+    double_width: 0,
+};
+
+/// Grundig
+pub struct Grundig;
+
+impl Protocol for Grundig {
+    type Cmd = GrundigCommand;
+}
+
+impl BiPhaseProtocol for Grundig {
+    const DESCRIPTOR: &'static BiPhase = &GRUNDIG_BIPHASE;
+
+    fn unpack(bits: u64, _nbits: u8) -> Option<GrundigCommand> {
+        // Sent LSB first
+        let cmd = (bits as u16).reverse_bits() >> (16 - NBITS);
+        Some(GrundigCommand::new(cmd))
+    }
+
+    fn pack(cmd: &GrundigCommand) -> (u64, u8) {
+        let bits = (cmd.cmd & GrundigCommand::INFO).reverse_bits() >> (16 - NBITS);
+        (bits.into(), NBITS)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GrundigCommand {
+    /// Command, 9 bits
+    pub cmd: u16,
+}
+
+impl GrundigCommand {
+    /// Command of the info frame
+    pub const INFO: u16 = 0x1FF;
+
+    pub const fn new(cmd: u16) -> Self {
+        Self { cmd }
+    }
+
+    /// Is this an info frame
+    pub const fn is_info(&self) -> bool {
+        self.cmd == Self::INFO
+    }
+}
+
+impl Command for GrundigCommand {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+}
+
+impl AddressCommand for GrundigCommand {
+    const ID: ProtocolId = ProtocolId::Grundig;
+
+    fn raw(&self) -> u64 {
+        self.cmd.into()
+    }
+
+    fn address(&self) -> u32 {
+        0
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<GrundigCommand> {
+        let cmd: u16 = cmd.try_into().ok()?;
+
+        if addr != 0 || cmd > Self::INFO {
+            return None;
+        }
+
+        Some(GrundigCommand::new(cmd))
+    }
+}
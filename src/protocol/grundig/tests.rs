@@ -0,0 +1,64 @@
+use crate::{
+    cmd::AddressCommand,
+    protocol::{grundig::GrundigCommand, Grundig},
+    receiver::BufferInputReceiver,
+    sender::PulsedataBuffer,
+};
+
+#[test]
+fn grundig_command() {
+    assert_eq!(
+        GrundigCommand::create(0, 0x1FF),
+        Some(GrundigCommand::new(0x1FF))
+    );
+    assert_eq!(GrundigCommand::create(0, 0x200), None);
+    assert_eq!(GrundigCommand::create(1, 0x10), None);
+    assert!(GrundigCommand::new(GrundigCommand::INFO).is_info());
+}
+
+#[test]
+fn encode_frame() {
+    let mut ptb = PulsedataBuffer::<64>::new();
+    ptb.load::<Grundig, 1_000_000>(&GrundigCommand::new(0x100));
+
+    // Pre-pulse, pause, start bit and eight zeros, merged with the start bit space
+    assert_eq!(&ptb.buffer()[..6], &[0, 528, 2640, 528, 1056, 528]);
+    // The last bit, a one
+    assert_eq!(ptb.buffer().last(), Some(&1056));
+}
+
+#[test]
+fn all_commands() {
+    const SAMPLERATE: u32 = 40_000;
+
+    let mut ptb = PulsedataBuffer::<64>::new();
+
+    for cmdnum in 0..=GrundigCommand::INFO {
+        ptb.reset();
+
+        let cmd = GrundigCommand::new(cmdnum);
+        ptb.load::<Grundig, SAMPLERATE>(&cmd);
+
+        let mut r = BufferInputReceiver::<Grundig>::with_frequenzy(SAMPLERATE);
+        let cmdres = r.iter(ptb.buffer()).next();
+
+        assert_eq!(cmdres, Some(cmd));
+    }
+}
+
+#[test]
+fn timer_resolution() {
+    test_freq::<20_000>();
+    test_freq::<40_000>();
+    test_freq::<48_000_000>();
+}
+
+fn test_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<64>::new();
+    let cmd = GrundigCommand::new(0x0A5);
+    ptb.load::<Grundig, F>(&cmd);
+
+    let mut r = BufferInputReceiver::<Grundig>::with_frequenzy(F);
+
+    assert_eq!(r.iter(ptb.buffer()).next(), Some(cmd));
+}
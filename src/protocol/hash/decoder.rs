@@ -0,0 +1,132 @@
+use crate::{
+    protocol::{
+        hash::{self, HashCommand, FNV_BASIS},
+        Hash,
+    },
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecoderBuilder, ProtocolDecoder, State,
+    },
+};
+
+/// Spaces this long end the frame, in us
+const GAP: u32 = 10_000;
+/// Shortest frame, in marks and spaces
+const MIN_LEN: u32 = 12;
+
+const PULSE: [u32; 8] = [GAP, 0, 0, 0, 0, 0, 0, 0];
+const TOL: [u32; 8] = [0; 8];
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Hash {
+    type Decoder = HashDecoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        let spans = PulseSpans::new(freq, &PULSE, &TOL);
+
+        HashDecoder {
+            state: HashState::Idle,
+            freq,
+            hash: FNV_BASIS,
+            prev: [0; 2],
+            gap: spans.spans[0].low(),
+            spans,
+        }
+    }
+}
+
+pub struct HashDecoder<Mono: InfraMonotonic> {
+    pub(crate) state: HashState,
+    freq: u32,
+    hash: u32,
+    /// The last two lengths, in us
+    prev: [u32; 2],
+    gap: Mono::Duration,
+    spans: PulseSpans<Mono>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HashState {
+    /// Waiting for the first mark
+    Idle,
+    /// Number of marks and spaces received
+    Receiving(u32),
+    /// Frame received
+    Done,
+}
+
+impl From<HashState> for State {
+    fn from(state: HashState) -> Self {
+        match state {
+            HashState::Idle => State::Idle,
+            HashState::Receiving(_) => State::Receiving,
+            HashState::Done => State::Done,
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic> HashDecoder<Mono> {
+    /// Add a mark or space of `dt` to the hash
+    fn add(&mut self, n: u32, dt: Mono::Duration) -> HashState {
+        let len = Mono::to_micros(self.freq, dt);
+
+        if n >= 2 {
+            self.hash = hash::add(self.hash, self.prev[0], len);
+        }
+        self.prev = [self.prev[1], len];
+
+        HashState::Receiving(n.saturating_add(1))
+    }
+
+    /// End of the frame after `n` marks and spaces
+    fn end(n: u32) -> HashState {
+        if n >= MIN_LEN {
+            HashState::Done
+        } else {
+            HashState::Idle
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic> ProtocolDecoder<Hash, Mono> for HashDecoder<Mono> {
+    fn event(&mut self, rising: bool, dt: Mono::Duration) -> State {
+        self.state = match (self.state, rising) {
+            // End of the first mark
+            (HashState::Idle, false) => {
+                self.hash = FNV_BASIS;
+                self.add(0, dt)
+            }
+            (HashState::Receiving(n), true) if dt >= self.gap => Self::end(n),
+            (HashState::Receiving(n), _) => self.add(n, dt),
+            (state, _) => state,
+        };
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<HashCommand> {
+        match self.state {
+            HashState::Done => Some(HashCommand::new(self.hash)),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = HashState::Idle;
+        self.hash = FNV_BASIS;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.spans
+    }
+
+    fn timeout(&mut self, dt: Mono::Duration) -> bool {
+        if let HashState::Receiving(n) = self.state {
+            if dt >= self.gap {
+                self.state = Self::end(n);
+            }
+        }
+
+        self.state == HashState::Done
+    }
+}
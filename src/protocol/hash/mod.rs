@@ -0,0 +1,109 @@
+//! Hash of an unknown protocol
+//!
+//! Fallback for remotes without a protocol implementation, as the hash decoder of IRremote. Every
+//! mark is compared with the mark before it, and every space with the space before it, giving
+//! shorter, equal or longer, within 20 %. The comparisons are hashed with 32 bit FNV-1 into a
+//! fingerprint of the frame that doesn't depend on the exact timing.
+//!
+//! The hash is the command of a [`HashCommand`], with address 0, so buttons can be mapped to
+//! actions with a `RemoteControlModel`. Different buttons can have the same hash, and a remote
+//! sending toggle bits has two hashes for every button.
+//!
+//! A frame is complete when the space after it is at least 10 ms long, so it's reported on the
+//! first edge of the next frame. Frames shorter than 12 marks and spaces, like Nec repeats,
+//! are ignored. There is no encoder.
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::Protocol,
+    ProtocolId,
+};
+
+pub mod decoder;
+
+#[cfg(all(test, feature = "nec", feature = "remotes"))]
+mod tests;
+
+pub use decoder::HashDecoder;
+
+/// FNV-1 offset basis
+const FNV_BASIS: u32 = 2_166_136_261;
+/// FNV-1 prime
+const FNV_PRIME: u32 = 16_777_619;
+
+/// Hash of the frame
+pub struct Hash;
+
+impl Protocol for Hash {
+    type Cmd = HashCommand;
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HashCommand {
+    /// Fingerprint of the frame
+    pub hash: u32,
+}
+
+impl HashCommand {
+    pub const fn new(hash: u32) -> Self {
+        Self { hash }
+    }
+
+    /// Hash a frame of mark and space lengths, starting with a mark
+    pub fn from_pulses(pulses: &[u32]) -> Self {
+        let hash = pulses
+            .iter()
+            .zip(pulses.iter().skip(2))
+            .fold(FNV_BASIS, |hash, (prev, len)| add(hash, *prev, *len));
+
+        Self { hash }
+    }
+}
+
+/// Add the comparison of `len` with the length `prev` before it to `hash`
+pub(crate) fn add(hash: u32, prev: u32, len: u32) -> u32 {
+    let (prev, len) = (u64::from(prev), u64::from(len));
+
+    let value = if len * 10 < prev * 8 {
+        0
+    } else if prev * 10 < len * 8 {
+        2
+    } else {
+        1
+    };
+
+    hash.wrapping_mul(FNV_PRIME) ^ value
+}
+
+impl Command for HashCommand {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+}
+
+impl AddressCommand for HashCommand {
+    const ID: ProtocolId = ProtocolId::Hash;
+
+    fn raw(&self) -> u64 {
+        self.hash.into()
+    }
+
+    /// Always 0
+    fn address(&self) -> u32 {
+        0
+    }
+
+    /// The hash
+    fn command(&self) -> u32 {
+        self.hash
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        if addr != 0 {
+            return None;
+        }
+
+        Some(Self { hash: cmd })
+    }
+}
@@ -0,0 +1,167 @@
+use std::vec::Vec;
+
+use crate::{
+    cmd::AddressCommand,
+    protocol::{
+        hash::HashCommand,
+        nec::{NecCommand, SamsungNecCommand},
+        Hash, Nec, SamsungNec,
+    },
+    receiver::{BufferInputReceiver, MultiReceiver, NoPin, PeriodicPoll, Receiver},
+    remotecontrol::{Action, Button, RemoteControlModel},
+    sender::{ProtocolEncoder, PulsedataBuffer},
+    ProtocolId,
+};
+
+/// Encoded command followed by the gap to the next frame, at 1 MHz
+fn frame<P: ProtocolEncoder<1_000_000>>(cmd: &P::Cmd) -> Vec<u32> {
+    let mut ptb = PulsedataBuffer::<128>::new();
+    ptb.load::<P, 1_000_000>(cmd);
+
+    let mut buf = Vec::from(ptb.buffer());
+    buf.push(40_000);
+    buf
+}
+
+fn nec(cmd: u8) -> NecCommand {
+    NecCommand {
+        addr: 0x12,
+        cmd,
+        repeat: false,
+    }
+}
+
+fn hashes(buf: &[u32]) -> Vec<HashCommand> {
+    let mut brecv = BufferInputReceiver::<Hash>::with_frequenzy(1_000_000);
+    brecv.iter(buf).collect()
+}
+
+#[test]
+fn hash_is_stable() {
+    let buf = frame::<Nec>(&nec(0x34));
+    let cmds = hashes(&buf);
+
+    // Without the leading zero and the gap
+    let expected = HashCommand::from_pulses(&buf[1..buf.len() - 1]);
+    assert_eq!(cmds, [expected]);
+
+    // Marks received 15 % longer
+    let stretched: Vec<u32> = buf
+        .iter()
+        .enumerate()
+        .map(|(i, len)| if i & 0x1 == 1 { len * 115 / 100 } else { *len })
+        .collect();
+    assert_eq!(hashes(&stretched), [expected]);
+}
+
+#[test]
+fn different_buttons() {
+    let a = hashes(&frame::<Nec>(&nec(0x34)));
+    let b = hashes(&frame::<Nec>(&nec(0x35)));
+    let samsung = hashes(&frame::<SamsungNec>(&SamsungNecCommand {
+        addr: 0x12,
+        cmd: 0x34,
+        repeat: false,
+    }));
+
+    assert_eq!(a.len(), 1);
+    assert_ne!(a, b);
+    // Same bits, but the header mark is as long as the header space
+    assert_ne!(a, samsung);
+}
+
+#[test]
+fn short_frames_ignored() {
+    // Nec repeats
+    let buf = [0, 9000, 2250, 560, 96_000, 9000, 2250, 560, 96_000];
+    assert!(hashes(&buf).is_empty());
+}
+
+#[test]
+fn frame_ends_with_gap() {
+    let mut buf = frame::<Nec>(&nec(0x34));
+    buf.pop();
+    assert!(hashes(&buf).is_empty());
+
+    // Two frames back to back
+    let mut twice = frame::<Nec>(&nec(0x34));
+    twice.extend_from_slice(&frame::<Nec>(&nec(0x35))[1..]);
+    assert_eq!(
+        hashes(&twice),
+        [
+            hashes(&frame::<Nec>(&nec(0x34)))[0],
+            hashes(&frame::<Nec>(&nec(0x35)))[0]
+        ]
+    );
+}
+
+#[test]
+fn timeout() {
+    let buf = frame::<Nec>(&nec(0x34));
+    let expected = hashes(&buf);
+
+    // Without the gap, the frame is completed by the timeout
+    let mut recv = Receiver::<Hash>::new(1_000_000);
+    for (i, dt) in buf[..buf.len() - 1].iter().enumerate() {
+        assert_eq!(recv.event(*dt, i & 0x1 == 0), Ok(None));
+    }
+    assert_eq!(recv.timeout(5000), None);
+    assert_eq!(recv.timeout(10_000), Some(expected[0]));
+    assert_eq!(recv.timeout(20_000), None);
+
+    // Polled at 20 kHz, the frame is completed 10 ms into the pause
+    let mut poll = PeriodicPoll::<Hash>::new(20_000);
+    let mut polled = Vec::new();
+    for (i, dt) in buf[1..buf.len() - 1].iter().enumerate() {
+        for _ in 0..dt / 50 {
+            polled.extend(poll.poll(i & 0x1 == 0).unwrap());
+        }
+    }
+    for n in 0..400 {
+        if let Some(cmd) = poll.poll(false).unwrap() {
+            assert_eq!(n, 200);
+            polled.push(cmd);
+        }
+    }
+    assert_eq!(polled, expected);
+}
+
+#[derive(Debug, Default)]
+struct LearnedRemote;
+
+impl RemoteControlModel for LearnedRemote {
+    const MODEL: &'static str = "Learned";
+    const PROTOCOL: ProtocolId = ProtocolId::Hash;
+    const ADDRESS: u32 = 0;
+    type Cmd = HashCommand;
+    const BUTTONS: &'static [(u32, Action)] = &[(0x345E_7AAB, Action::Play)];
+}
+
+#[test]
+fn remote_control() {
+    let buf = frame::<Nec>(&nec(0x34));
+    let hash = HashCommand::from_pulses(&buf[1..buf.len() - 1]);
+    assert_eq!(hash.command(), LearnedRemote::BUTTONS[0].0);
+    assert_eq!(HashCommand::create(0, hash.hash), Some(hash));
+    assert_eq!(HashCommand::create(1, hash.hash), None);
+
+    let mut brecv =
+        BufferInputReceiver::<Hash, u32, Button<LearnedRemote>>::with_frequenzy(1_000_000);
+    let button = brecv.iter(&buf).next().unwrap();
+    assert_eq!(button.action(), Some(Action::Play));
+}
+
+#[test]
+fn multireceiver() {
+    let buf = frame::<Nec>(&nec(0x34));
+    let mut recv = MultiReceiver::<2, (Nec, Hash), NoPin>::new(1_000_000, NoPin);
+
+    let cmds: Vec<_> = buf
+        .iter()
+        .enumerate()
+        .flat_map(|(i, dt)| recv.event_generic_iter(*dt, i & 0x1 == 0))
+        .map(|cmd| cmd.protocol)
+        .collect();
+
+    assert_eq!(cmds, [ProtocolId::Nec, ProtocolId::Hash]);
+}
@@ -0,0 +1,145 @@
+use crate::{
+    protocol::{
+        jvc::{JvcCommand, DATA_HIGH, HEADER_LOW, ONE_LOW, ZERO_LOW},
+        Jvc,
+    },
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecoderBuilder, DecodingError, ProtocolDecoder, State,
+    },
+};
+
+const PULSE: [u32; 8] = [
+    // The header mark, or the pause before a headerless frame. 7 to 45 ms
+    26_000,
+    HEADER_LOW,
+    DATA_HIGH,
+    (DATA_HIGH + ZERO_LOW),
+    (DATA_HIGH + ONE_LOW),
+    0,
+    0,
+    0,
+];
+const TOL: [u32; 8] = [73, 10, 25, 20, 15, 0, 0, 0];
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Jvc {
+    type Decoder = JvcDecoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        JvcDecoder {
+            state: JvcState::Idle,
+            bitbuf: 0,
+            repeat: false,
+            dt_save: Mono::ZERO_DURATION,
+            spans: PulseSpans::new(freq, &PULSE, &TOL),
+        }
+    }
+}
+
+pub struct JvcDecoder<Mono: InfraMonotonic> {
+    state: JvcState,
+    bitbuf: u16,
+    repeat: bool,
+    dt_save: Mono::Duration,
+    spans: PulseSpans<Mono>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum JvcState {
+    Idle,
+    Data(u8),
+    Done,
+    Err(DecodingError),
+}
+
+impl From<JvcState> for State {
+    fn from(state: JvcState) -> Self {
+        match state {
+            JvcState::Idle => State::Idle,
+            JvcState::Data(_) => State::Receiving,
+            JvcState::Done => State::Done,
+            JvcState::Err(err) => State::Error(err),
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic> ProtocolDecoder<Jvc, Mono> for JvcDecoder<Mono> {
+    #[rustfmt::skip]
+    fn event(&mut self, rising: bool, dt: Mono::Duration) -> State {
+        use JvcPulse::*;
+        use JvcState::*;
+
+        if rising {
+            let mark = self.spans.get::<JvcPulse>(self.dt_save).unwrap_or(Invalid);
+            let space = self.spans.get::<JvcPulse>(dt).unwrap_or(Invalid);
+            let total = self.spans.get::<JvcPulse>(self.dt_save + dt).unwrap_or(Invalid);
+
+            self.state = match (self.state, mark, space, total) {
+                // Header
+                (Idle,      Long,   HeaderLow,  _)  => { self.repeat = false; Data(0) }
+                // Stop bit of the previous frame followed by the pause
+                (Idle,      Short,  Long,       _)  => { self.repeat = true; Data(0) }
+                (Idle,      _,      _,          _)  => Idle,
+
+                (Data(15),  _,      _,          One)    => { self.bitbuf |= 1 << 15; Done }
+                (Data(15),  _,      _,          Zero)   => Done,
+                (Data(bit), _,      _,          One)    => { self.bitbuf |= 1 << bit; Data(bit + 1) }
+                (Data(bit), _,      _,          Zero)   => Data(bit + 1),
+                (Data(_),   _,      _,          _)      => Err(DecodingError::Data),
+
+                (Done,      _,      _,          _)      => Done,
+                (Err(err),  _,      _,          _)      => Err(err),
+            };
+
+            self.dt_save = Mono::ZERO_DURATION;
+        } else {
+            self.dt_save = dt;
+        }
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<JvcCommand> {
+        match self.state {
+            JvcState::Done => Some(JvcCommand::unpack(self.bitbuf, self.repeat)),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = JvcState::Idle;
+        self.bitbuf = 0;
+        self.dt_save = Mono::ZERO_DURATION;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.spans
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum JvcPulse {
+    /// Header mark or the pause between frames
+    Long,
+    HeaderLow,
+    /// Data or stop bit mark
+    Short,
+    Zero,
+    One,
+    Invalid,
+}
+
+impl From<usize> for JvcPulse {
+    fn from(v: usize) -> Self {
+        match v {
+            0 => JvcPulse::Long,
+            1 => JvcPulse::HeaderLow,
+            2 => JvcPulse::Short,
+            3 => JvcPulse::Zero,
+            4 => JvcPulse::One,
+            _ => JvcPulse::Invalid,
+        }
+    }
+}
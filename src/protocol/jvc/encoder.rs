@@ -0,0 +1,62 @@
+use crate::{
+    protocol::{
+        jvc::{DATA_HIGH, HEADER_HIGH, HEADER_LOW, ONE_LOW, REPEAT_PERIOD, ZERO_LOW},
+        utils::scale_with_samplerate,
+        Jvc,
+    },
+    sender::ProtocolEncoder,
+};
+
+impl<const F: u32> ProtocolEncoder<F> for Jvc {
+    type EncoderData = [u32; 6];
+    const DATA: Self::EncoderData = [
+        scale_with_samplerate(HEADER_HIGH, F),
+        scale_with_samplerate(HEADER_LOW, F),
+        scale_with_samplerate(DATA_HIGH, F),
+        scale_with_samplerate(ZERO_LOW, F),
+        scale_with_samplerate(ONE_LOW, F),
+        scale_with_samplerate(REPEAT_PERIOD, F),
+    ];
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        let [header_high, header_low, data_high, zero_low, one_low, period] =
+            <Self as ProtocolEncoder<F>>::DATA;
+
+        let bits = cmd.pack();
+        let mut bi;
+
+        if cmd.repeat {
+            // The pause is filled in when the length of the frame is known
+            bi = 1;
+        } else {
+            b[0] = 0;
+            b[1] = header_high;
+            b[2] = header_low;
+            bi = 3;
+        }
+
+        let mut frame_len = 0;
+
+        for i in 0..16 {
+            let low = if (bits >> i) & 1 != 0 {
+                one_low
+            } else {
+                zero_low
+            };
+            b[bi] = data_high;
+            b[bi + 1] = low;
+            frame_len += data_high + low;
+            bi += 2;
+        }
+
+        // Stop bit
+        b[bi] = data_high;
+        bi += 1;
+
+        if cmd.repeat {
+            b[0] = period.saturating_sub(frame_len + data_high);
+        }
+
+        bi
+    }
+}
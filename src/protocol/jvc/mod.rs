@@ -0,0 +1,93 @@
+//! JVC
+//!
+//! Pulse distance coded protocol, similar to Nec. The header is a 8.4 ms mark and a 4.2 ms space,
+//! followed by the 8 bit address and the 8 bit command, LSB first, and a stop bit.
+//!
+//! When a button is held the frame is repeated every 50 - 60 ms, but without the header.
+//! The decoder reports these headerless frames as repeats.
+
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::Protocol,
+    ProtocolId,
+};
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+const HEADER_HIGH: u32 = 8400;
+const HEADER_LOW: u32 = 4200;
+const DATA_HIGH: u32 = 526;
+const ZERO_LOW: u32 = 526;
+const ONE_LOW: u32 = 1574;
+/// Frame period of the headerless repeats
+const REPEAT_PERIOD: u32 = 55_000;
+
+/// JVC
+pub struct Jvc;
+
+impl Protocol for Jvc {
+    type Cmd = JvcCommand;
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct JvcCommand {
+    pub addr: u8,
+    pub cmd: u8,
+    /// Frame was sent without header
+    pub repeat: bool,
+}
+
+impl JvcCommand {
+    pub const fn new(addr: u8, cmd: u8) -> Self {
+        Self {
+            addr,
+            cmd,
+            repeat: false,
+        }
+    }
+
+    pub const fn unpack(bits: u16, repeat: bool) -> Self {
+        Self {
+            addr: (bits & 0xFF) as u8,
+            cmd: (bits >> 8) as u8,
+            repeat,
+        }
+    }
+
+    pub fn pack(&self) -> u16 {
+        u16::from(self.addr) | u16::from(self.cmd) << 8
+    }
+}
+
+impl Command for JvcCommand {
+    fn is_repeat(&self) -> bool {
+        self.repeat
+    }
+}
+
+impl AddressCommand for JvcCommand {
+    const ID: ProtocolId = ProtocolId::Jvc;
+
+    fn raw(&self) -> u64 {
+        self.pack().into()
+    }
+
+    fn address(&self) -> u32 {
+        self.addr.into()
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        Some(JvcCommand::new(addr.try_into().ok()?, cmd.try_into().ok()?))
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    protocol::{jvc::JvcCommand, Jvc},
+    receiver::BufferInputReceiver,
+    sender::PulsedataBuffer,
+};
+
+#[test]
+fn cmd_pack_unpack() {
+    let cmd = JvcCommand::new(0x03, 0x17);
+    assert_eq!(cmd.pack(), 0x1703);
+    assert_eq!(JvcCommand::unpack(cmd.pack(), false), cmd);
+}
+
+#[test]
+fn repeats() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+
+    let cmd = JvcCommand::new(0x03, 0x17);
+    let repeat = JvcCommand {
+        repeat: true,
+        ..cmd
+    };
+
+    ptb.load::<Jvc, FREQ>(&cmd);
+    ptb.load::<Jvc, FREQ>(&repeat);
+    ptb.load::<Jvc, FREQ>(&repeat);
+
+    let mut brecv = BufferInputReceiver::<Jvc>::with_frequenzy(FREQ);
+    let cmds = brecv.iter(ptb.buffer()).collect::<std::vec::Vec<_>>();
+
+    assert_eq!(cmds, [cmd, repeat, repeat]);
+}
+
+#[test]
+fn all_commands() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<96>::new();
+
+    for address in 0..255 {
+        for cmdnum in 0..255 {
+            ptb.reset();
+            let cmd = JvcCommand::new(address, cmdnum);
+            ptb.load::<Jvc, FREQ>(&cmd);
+
+            let mut brecv = BufferInputReceiver::<Jvc>::with_frequenzy(FREQ);
+            let cmdres = brecv.iter(ptb.buffer()).next().unwrap();
+
+            assert_eq!(cmd, cmdres);
+        }
+    }
+}
+
+#[test]
+fn clock_frequencies() {
+    one_freq::<20_000>();
+    one_freq::<40_000>();
+    one_freq::<48_000_000>();
+}
+
+fn one_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<128>::new();
+    let cmd = JvcCommand::new(0xC5, 0x3A);
+    let repeat = JvcCommand {
+        repeat: true,
+        ..cmd
+    };
+    ptb.load::<Jvc, F>(&cmd);
+    ptb.load::<Jvc, F>(&repeat);
+
+    let mut brecv = BufferInputReceiver::<Jvc>::with_frequenzy(F);
+    let cmds = brecv.iter(ptb.buffer()).collect::<std::vec::Vec<_>>();
+
+    assert_eq!(cmds, [cmd, repeat], "Samplerate: {}", F);
+}
@@ -0,0 +1,140 @@
+use crate::{
+    protocol::{
+        kaseikyo::{KaseikyoCommand, DATA_HIGH, HEADER_HIGH, HEADER_LOW, ONE_LOW, ZERO_LOW},
+        Kaseikyo,
+    },
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecoderBuilder, DecodingError, ProtocolDecoder, State,
+    },
+};
+
+const PULSE: [u32; 8] = [
+    (HEADER_HIGH + HEADER_LOW),
+    (DATA_HIGH + ZERO_LOW),
+    (DATA_HIGH + ONE_LOW),
+    0,
+    0,
+    0,
+    0,
+    0,
+];
+const TOL: [u32; 8] = [8, 15, 10, 0, 0, 0, 0, 0];
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Kaseikyo {
+    type Decoder = KaseikyoDecoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        KaseikyoDecoder {
+            state: KaseikyoState::Idle,
+            buf: 0,
+            dt_save: Mono::ZERO_DURATION,
+            spans: PulseSpans::new(freq, &PULSE, &TOL),
+        }
+    }
+}
+
+pub struct KaseikyoDecoder<Mono: InfraMonotonic> {
+    state: KaseikyoState,
+    buf: u64,
+    dt_save: Mono::Duration,
+    spans: PulseSpans<Mono>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum KaseikyoState {
+    Idle,
+    Data(u8),
+    Done,
+    Err(DecodingError),
+}
+
+impl From<KaseikyoState> for State {
+    fn from(state: KaseikyoState) -> Self {
+        match state {
+            KaseikyoState::Idle => State::Idle,
+            KaseikyoState::Data(_) => State::Receiving,
+            KaseikyoState::Done => State::Done,
+            KaseikyoState::Err(err) => State::Error(err),
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic> ProtocolDecoder<Kaseikyo, Mono> for KaseikyoDecoder<Mono> {
+    #[rustfmt::skip]
+    fn event(&mut self, rising: bool, dt: Mono::Duration) -> State {
+        use KaseikyoState::*;
+        use PulseWidth::*;
+
+        if rising {
+            let pulsewidth = self.spans.get::<PulseWidth>(self.dt_save + dt)
+                .unwrap_or(PulseWidth::Fail);
+
+            self.state = match (self.state, pulsewidth) {
+                (Idle,          Sync)   => { self.buf = 0; Data(0) }
+                (Idle,          _)      => Idle,
+                (Data(47),      Zero)   => self.validate(),
+                (Data(47),      One)    => { self.buf |= 1 << 47; self.validate() }
+                (Data(idx),     Zero)   => Data(idx + 1),
+                (Data(idx),     One)    => { self.buf |= 1 << idx; Data(idx + 1) }
+                (Data(_),       _)      => Err(DecodingError::Data),
+                (Done,          _)      => Done,
+                (Err(err),      _)      => Err(err),
+            };
+
+            self.dt_save = Mono::ZERO_DURATION;
+        } else {
+            self.dt_save = dt;
+        }
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<KaseikyoCommand> {
+        match self.state {
+            KaseikyoState::Done => KaseikyoCommand::unpack(self.buf),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = KaseikyoState::Idle;
+        self.buf = 0;
+        self.dt_save = Mono::ZERO_DURATION;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.spans
+    }
+}
+
+impl<Mono: InfraMonotonic> KaseikyoDecoder<Mono> {
+    fn validate(&self) -> KaseikyoState {
+        if KaseikyoCommand::validate(self.buf) {
+            KaseikyoState::Done
+        } else {
+            KaseikyoState::Err(DecodingError::Validation)
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum PulseWidth {
+    Sync,
+    Zero,
+    One,
+    Fail,
+}
+
+impl From<usize> for PulseWidth {
+    fn from(value: usize) -> Self {
+        match value {
+            0 => PulseWidth::Sync,
+            1 => PulseWidth::Zero,
+            2 => PulseWidth::One,
+            _ => PulseWidth::Fail,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{
+    protocol::{
+        kaseikyo::{DATA_HIGH, HEADER_HIGH, HEADER_LOW, ONE_LOW, ZERO_LOW},
+        utils::scale_with_samplerate,
+        Kaseikyo,
+    },
+    sender::ProtocolEncoder,
+};
+
+impl<const F: u32> ProtocolEncoder<F> for Kaseikyo {
+    type EncoderData = [u32; 5];
+    const DATA: Self::EncoderData = [
+        scale_with_samplerate(HEADER_HIGH, F),
+        scale_with_samplerate(HEADER_LOW, F),
+        scale_with_samplerate(DATA_HIGH, F),
+        scale_with_samplerate(ZERO_LOW, F),
+        scale_with_samplerate(ONE_LOW, F),
+    ];
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        let [header_high, header_low, data_high, zero_low, one_low] =
+            <Self as ProtocolEncoder<F>>::DATA;
+
+        b[0] = 0;
+        b[1] = header_high;
+        b[2] = header_low;
+
+        let bits = cmd.pack();
+        let mut bi = 3;
+
+        for i in 0..48 {
+            b[bi] = data_high;
+            b[bi + 1] = if (bits >> i) & 1 != 0 {
+                one_low
+            } else {
+                zero_low
+            };
+            bi += 2;
+        }
+
+        // Stop bit
+        b[bi] = data_high;
+
+        bi + 1
+    }
+}
@@ -0,0 +1,180 @@
+//! Kaseikyo (Japanese Association for Electric Home Appliances) 48 bit protocol
+//!
+//! Used by Panasonic, Denon, Mitsubishi, Sharp, JVC and others.
+//!
+//! Pulse distance coding with a unit time of 432 us. After the header the bits are sent LSB first:
+//!
+//! | Bits  | Field                                   |
+//! |-------|-----------------------------------------|
+//! | 0-15  | Vendor id                               |
+//! | 16-19 | Vendor parity, xor of the vendor nibbles|
+//! | 20-23 | Genre / device                          |
+//! | 24-31 | Sub-device                              |
+//! | 32-39 | Command                                 |
+//! | 40-47 | Checksum, xor of byte 2, 3 and 4        |
+//!
+
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::Protocol,
+    ProtocolId,
+};
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+const UNIT: u32 = 432;
+const HEADER_HIGH: u32 = 8 * UNIT;
+const HEADER_LOW: u32 = 4 * UNIT;
+const DATA_HIGH: u32 = UNIT;
+const ZERO_LOW: u32 = UNIT;
+const ONE_LOW: u32 = 3 * UNIT;
+
+/// Kaseikyo protocol
+pub struct Kaseikyo;
+
+impl Protocol for Kaseikyo {
+    type Cmd = KaseikyoCommand;
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Vendor
+pub enum Vendor {
+    Panasonic,
+    Denon,
+    Mitsubishi,
+    Sharp,
+    Jvc,
+    /// Vendor id without a known vendor
+    Other(u16),
+}
+
+impl Vendor {
+    /// The 16 bit vendor id
+    pub const fn id(&self) -> u16 {
+        match self {
+            Vendor::Panasonic => 0x2002,
+            Vendor::Denon => 0x3254,
+            Vendor::Mitsubishi => 0xCB23,
+            Vendor::Sharp => 0x5AAA,
+            Vendor::Jvc => 0x0103,
+            Vendor::Other(id) => *id,
+        }
+    }
+
+    /// The 4 bit parity of the vendor id
+    pub const fn parity(&self) -> u8 {
+        let id = self.id();
+        let p = (id ^ (id >> 8)) as u8;
+        (p ^ (p >> 4)) & 0xF
+    }
+}
+
+impl From<u16> for Vendor {
+    fn from(id: u16) -> Self {
+        match id {
+            0x2002 => Vendor::Panasonic,
+            0x3254 => Vendor::Denon,
+            0xCB23 => Vendor::Mitsubishi,
+            0x5AAA => Vendor::Sharp,
+            0x0103 => Vendor::Jvc,
+            id => Vendor::Other(id),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct KaseikyoCommand {
+    pub vendor: Vendor,
+    /// Genre / device. 4 bits, the high bits are dropped
+    pub device: u8,
+    pub subdevice: u8,
+    pub cmd: u8,
+}
+
+impl KaseikyoCommand {
+    pub const fn new(vendor: Vendor, device: u8, subdevice: u8, cmd: u8) -> Self {
+        Self {
+            vendor,
+            device,
+            subdevice,
+            cmd,
+        }
+    }
+
+    /// Check the vendor parity and the checksum
+    pub fn validate(bits: u64) -> bool {
+        let [v0, v1, b2, b3, b4, checksum, ..] = bits.to_le_bytes();
+
+        let vendor = Vendor::Other(u16::from_le_bytes([v0, v1]));
+
+        vendor.parity() == b2 & 0xF && b2 ^ b3 ^ b4 == checksum
+    }
+
+    pub fn unpack(bits: u64) -> Option<Self> {
+        if !Self::validate(bits) {
+            return None;
+        }
+
+        let [v0, v1, b2, b3, b4, ..] = bits.to_le_bytes();
+
+        Some(Self {
+            vendor: u16::from_le_bytes([v0, v1]).into(),
+            device: b2 >> 4,
+            subdevice: b3,
+            cmd: b4,
+        })
+    }
+
+    pub fn pack(&self) -> u64 {
+        let [v0, v1] = self.vendor.id().to_le_bytes();
+        let b2 = self.vendor.parity() | (self.device & 0xF) << 4;
+        let b3 = self.subdevice;
+        let b4 = self.cmd;
+
+        u64::from_le_bytes([v0, v1, b2, b3, b4, b2 ^ b3 ^ b4, 0, 0])
+    }
+}
+
+impl Command for KaseikyoCommand {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+}
+
+impl AddressCommand for KaseikyoCommand {
+    const ID: ProtocolId = ProtocolId::Kaseikyo;
+
+    fn raw(&self) -> u64 {
+        self.pack()
+    }
+
+    /// Vendor id in bit 12 - 27, device in bit 8 - 11 and sub-device in bit 0 - 7
+    fn address(&self) -> u32 {
+        u32::from(self.vendor.id()) << 12
+            | u32::from(self.device & 0xF) << 8
+            | u32::from(self.subdevice)
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        let vendor: u16 = (addr >> 12).try_into().ok()?;
+
+        Some(KaseikyoCommand::new(
+            vendor.into(),
+            ((addr >> 8) & 0xF) as u8,
+            (addr & 0xFF) as u8,
+            cmd.try_into().ok()?,
+        ))
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    cmd::AddressCommand,
+    protocol::{
+        kaseikyo::{KaseikyoCommand, Vendor},
+        Kaseikyo,
+    },
+    receiver::{BufferInputReceiver, DecodingError},
+    sender::PulsedataBuffer,
+    Receiver,
+};
+
+#[test]
+fn vendor_parity() {
+    assert_eq!(Vendor::Panasonic.parity(), 0);
+    assert_eq!(Vendor::Denon.parity(), 0);
+    assert_eq!(Vendor::Jvc.parity(), 2);
+    assert_eq!(Vendor::from(0x5AAA), Vendor::Sharp);
+    assert_eq!(Vendor::from(0x1234), Vendor::Other(0x1234));
+}
+
+#[test]
+fn cmd_pack_unpack() {
+    let cmd = KaseikyoCommand::new(Vendor::Denon, 4, 1, 1);
+    let bits = cmd.pack();
+
+    assert_eq!(bits, 0x4001_0140_3254);
+    assert!(KaseikyoCommand::validate(bits));
+    assert_eq!(KaseikyoCommand::unpack(bits), Some(cmd));
+
+    // Bad checksum
+    assert_eq!(KaseikyoCommand::unpack(bits ^ (1 << 40)), None);
+    // Bad vendor parity
+    assert_eq!(KaseikyoCommand::unpack(bits ^ (1 << 3)), None);
+
+    let cmd = KaseikyoCommand::new(Vendor::Jvc, 0xA, 0x5C, 0x33);
+    assert_eq!(KaseikyoCommand::unpack(cmd.pack()), Some(cmd));
+
+    // The device is 4 bits, the high bits don't reach the parity
+    let wide = KaseikyoCommand::new(Vendor::Jvc, 0x3A, 0x5C, 0x33);
+    assert_eq!(wide.pack(), cmd.pack());
+    assert_eq!(wide.address(), cmd.address());
+    assert_eq!(KaseikyoCommand::unpack(wide.pack()), Some(cmd));
+}
+
+#[test]
+fn address_command() {
+    let cmd = KaseikyoCommand::new(Vendor::Panasonic, 8, 0x12, 0x3D);
+    assert_eq!(cmd.address(), 0x0200_2812);
+    assert_eq!(
+        KaseikyoCommand::create(cmd.address(), cmd.command()),
+        Some(cmd)
+    );
+}
+
+#[test]
+fn denon_frames() {
+    use std::vec::Vec;
+
+    // Denon remote. The second frame has a broken vendor id
+    #[rustfmt::skip]
+    let dists = &[
+        0, 136, 65, 18, 16, 18, 15, 19, 49, 18, 15, 19, 48, 19, 15, 19, 48, 19, 15, 18, 15, 19, 48,
+        19, 15, 19, 14, 19, 49, 18, 49, 18, 15, 19, 15, 19, 14, 19, 15, 19, 15, 18, 15, 19, 15, 19,
+        14, 19, 48, 19, 15, 19, 49, 18, 16, 17, 16, 18, 16, 18, 16, 17, 16, 18, 16, 18, 15, 18, 49,
+        18, 16, 17, 17, 17, 16, 18, 16, 17, 16, 18, 16, 18, 15, 18, 16, 17, 17, 17, 16, 18, 16, 18,
+        15, 18, 16, 18, 49, 18, 16, 18, 2939, 138, 65, 17, 16, 18, 16, 18, 49, 18, 16, 17, 50, 18,
+        15, 18, 50, 17, 16, 18, 16, 17, 50, 17, 16, 18, 16, 17, 18, 49, 18, 16, 16, 17, 18, 16, 18,
+        16, 17, 16, 19, 15, 18, 15, 18, 16, 17, 50, 19, 15, 19, 48, 17, 16, 19, 15, 18, 15, 19, 15,
+        18, 16, 18, 15, 19, 15, 19, 48, 19, 14, 19, 15, 19, 15, 19, 14, 19, 15, 19, 14, 19, 15, 19,
+        14, 19, 15, 19, 15, 18, 15, 19, 15, 19, 14, 20, 47, 19, 15, 19, 2940, 136, 65, 19, 15, 18,
+        15, 19, 49, 18, 15, 19, 48, 19, 15, 19, 48, 19, 15, 18, 15, 19, 48, 19, 15, 18, 15, 19, 49,
+        18, 49, 18, 15, 19, 15, 19, 14, 19, 15, 19, 15, 18, 15, 19, 15, 19, 14, 19, 48, 19, 15, 19,
+        48, 19, 15, 18, 17, 16, 19, 15, 19, 15, 18, 15, 19, 49, 19, 15, 18, 16, 17, 16, 18, 16, 18,
+        15, 18, 16, 19, 14, 18, 16, 18, 16, 17, 16, 18, 16, 18, 15, 18, 16, 18, 49, 18, 16, 18,
+    ];
+
+    let mut recv = Receiver::<Kaseikyo>::new(40_000);
+    let mut cmds = Vec::new();
+    let mut errors = Vec::new();
+
+    let mut edge = false;
+    for dt in dists {
+        edge = !edge;
+        match recv.event(*dt, edge) {
+            Ok(Some(cmd)) => cmds.push(cmd),
+            Ok(None) => (),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    assert_eq!(cmds, [KaseikyoCommand::new(Vendor::Denon, 4, 1, 1)]);
+    assert_eq!(errors, [DecodingError::Validation]);
+}
+
+#[test]
+fn all_vendors() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+
+    let vendors = [
+        Vendor::Panasonic,
+        Vendor::Denon,
+        Vendor::Mitsubishi,
+        Vendor::Sharp,
+        Vendor::Jvc,
+        Vendor::Other(0xBEEF),
+    ];
+
+    for vendor in vendors {
+        for cmdnum in 0..255 {
+            ptb.reset();
+            let cmd = KaseikyoCommand::new(vendor, cmdnum & 0xF, 255 - cmdnum, cmdnum);
+            ptb.load::<Kaseikyo, FREQ>(&cmd);
+
+            let mut brecv = BufferInputReceiver::<Kaseikyo>::with_frequenzy(FREQ);
+            let cmdres = brecv.iter(ptb.buffer()).next();
+
+            assert_eq!(cmdres, Some(cmd));
+        }
+    }
+}
+
+#[test]
+fn clock_frequencies() {
+    one_freq::<20_000>();
+    one_freq::<40_000>();
+    one_freq::<48_000_000>();
+}
+
+fn one_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<128>::new();
+    let cmd = KaseikyoCommand::new(Vendor::Panasonic, 8, 0, 0x3D);
+    ptb.load::<Kaseikyo, F>(&cmd);
+
+    let mut brecv = BufferInputReceiver::<Kaseikyo>::with_frequenzy(F);
+
+    assert_eq!(
+        brecv.iter(ptb.buffer()).next(),
+        Some(cmd),
+        "Samplerate: {}",
+        F
+    );
+}
@@ -0,0 +1,19 @@
+//! Lego Power Functions decoder. Decoded by the generic pulse distance decoder
+
+use crate::{
+    protocol::{pulsedistance::PulseDistanceDecoder, LegoPf},
+    receiver::{time::InfraMonotonic, DecoderBuilder},
+};
+
+pub use crate::protocol::pulsedistance::PulseDistanceState as LegoPfState;
+
+/// Lego Power Functions decoder
+pub type LegoPfDecoder<Mono> = PulseDistanceDecoder<Mono, LegoPf>;
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for LegoPf {
+    type Decoder = LegoPfDecoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        PulseDistanceDecoder::new(freq)
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{
+    protocol::{
+        lego_pf::MESSAGES,
+        pulsedistance::encoder::{encode, encoder_data},
+        utils::scale_with_samplerate,
+        LegoPf,
+    },
+    sender::ProtocolEncoder,
+};
+
+/// Entries of the burst, the space before every message, the start bit, the data and the stop bit
+const BURST_LEN: usize = MESSAGES * (4 + 2 * super::LEGO_PF_PULSEDISTANCE.nbits as usize);
+
+impl<const F: u32> ProtocolEncoder<F> for LegoPf {
+    type EncoderData = [u32; 7];
+    const DATA: [u32; 7] = encoder_data(&super::LEGO_PF_PULSEDISTANCE, F);
+
+    /// Encode the five message burst. The first space is the delay before the first message
+    ///
+    /// `b` needs 180 entries, nothing is encoded and 0 returned if it's shorter
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        let data = <Self as ProtocolEncoder<F>>::DATA;
+
+        if b.len() < BURST_LEN {
+            return 0;
+        }
+
+        let mut space = scale_with_samplerate(cmd.interval(0), F);
+        let mut bi = 0;
+
+        for n in 1..=MESSAGES {
+            // The first entry of a message is the space before it
+            let len = encode::<Self>(&data, cmd, &mut b[bi..]);
+            b[bi] = space;
+
+            // The space after the stop bit runs to the start of the next message
+            let msg_len: u32 = b[bi + 1..bi + len].iter().sum();
+            space = scale_with_samplerate(cmd.interval(n), F).saturating_sub(msg_len);
+
+            bi += len;
+        }
+
+        bi
+    }
+}
@@ -0,0 +1,197 @@
+//! Lego Power Functions
+//!
+//! A message is a start bit, 16 data bits, MSB first, and a stop bit. Every bit is a 158 us mark
+//! followed by a space, 1026 us for the start and stop bits, 553 us for a one and 263 us for
+//! a zero. The start bit is decoded and encoded as the header of the generic pulse distance
+//! engine. The 16 bits are four nibbles:
+//!
+//!  - Toggle, escape and the 2 bit channel
+//!  - Address and the 3 bit mode
+//!  - Data
+//!  - LRC, `0xF` xor the three other nibbles
+//!
+//! Every command is sent five times, the encoder emits the full burst with the retransmission
+//! timing of the channel, 180 entries, so the `Sender` buffer has to be at least that large.
+//! The decoder reports each of the five messages.
+
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::{
+        pulsedistance::{BitOrder, PulseDistance, PulseDistanceProtocol, Repeat},
+        Protocol,
+    },
+    ProtocolId,
+};
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+/// Max message length, the unit of the retransmission timing
+const TM: u32 = 16_000;
+
+/// Number of times a command is sent
+pub const MESSAGES: usize = 5;
+
+/// Lego Power Functions
+pub struct LegoPf;
+
+impl Protocol for LegoPf {
+    type Cmd = LegoPfCommand;
+}
+
+/// The start bit is the header and the stop bit the stop mark
+const LEGO_PF_PULSEDISTANCE: PulseDistance = PulseDistance {
+    header_high: 158,
+    header_low: 1026,
+    data_high: 158,
+    zero_low: 263,
+    one_low: 553,
+    nbits: 16,
+    order: BitOrder::Msb,
+    stop_bit: true,
+    repeat: Repeat::Frame,
+};
+
+impl PulseDistanceProtocol for LegoPf {
+    const DESCRIPTOR: &'static PulseDistance = &LEGO_PF_PULSEDISTANCE;
+
+    fn unpack(bits: u64, _repeat: bool) -> Option<LegoPfCommand> {
+        LegoPfCommand::unpack(bits as u16)
+    }
+
+    fn pack(cmd: &LegoPfCommand) -> u64 {
+        cmd.pack().into()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LegoPfCommand {
+    /// Toggled for every new command
+    pub toggle: bool,
+    /// Escape bit. Selects the combo PWM mode when set
+    pub escape: bool,
+    /// Channel, 0 - 3 for the channels 1 - 4
+    pub channel: u8,
+    /// Address bit, for the extra address space
+    pub address: bool,
+    /// Mode, 3 bits
+    pub mode: u8,
+    /// Data, 4 bits
+    pub data: u8,
+}
+
+impl LegoPfCommand {
+    /// Single output mode, PWM
+    pub const SINGLE_OUTPUT_PWM: u8 = 0b100;
+
+    pub const fn new(channel: u8, mode: u8, data: u8) -> Self {
+        Self {
+            toggle: false,
+            escape: false,
+            channel,
+            address: false,
+            mode,
+            data,
+        }
+    }
+
+    /// Set the PWM of an output. `blue` selects output B, otherwise output A, the red one.
+    /// `pwm` is 0 for float, 1 - 7 forward, 8 brake and 9 - 15 backward
+    pub const fn single_output(channel: u8, blue: bool, pwm: u8) -> Self {
+        Self::new(channel, Self::SINGLE_OUTPUT_PWM | blue as u8, pwm)
+    }
+
+    /// Unpack a message, `None` if the LRC is wrong
+    pub fn unpack(bits: u16) -> Option<Self> {
+        let nibble = |n: u16| (bits >> (12 - 4 * n) & 0xF) as u8;
+        let (n1, n2, data, lrc) = (nibble(0), nibble(1), nibble(2), nibble(3));
+
+        if lrc != 0xF ^ n1 ^ n2 ^ data {
+            return None;
+        }
+
+        Some(Self {
+            toggle: n1 & 0x8 != 0,
+            escape: n1 & 0x4 != 0,
+            channel: n1 & 0x3,
+            address: n2 & 0x8 != 0,
+            mode: n2 & 0x7,
+            data,
+        })
+    }
+
+    /// Pack the command into the 16 bits of a message
+    pub fn pack(&self) -> u16 {
+        let n1 = u8::from(self.toggle) << 3 | u8::from(self.escape) << 2 | self.channel & 0x3;
+        let n2 = u8::from(self.address) << 3 | self.mode & 0x7;
+        let data = self.data & 0xF;
+        let lrc = 0xF ^ n1 ^ n2 ^ data;
+
+        u16::from(n1) << 12 | u16::from(n2) << 8 | u16::from(data) << 4 | u16::from(lrc)
+    }
+
+    /// Time from the start of message `n`, and for 0 the start of the burst, to the start of
+    /// the next message. In us
+    pub const fn interval(&self, n: usize) -> u32 {
+        let ch = (self.channel & 0x3) as u32 + 1;
+
+        let tms = match n {
+            0 => 4 - ch,
+            1 | 2 => 5,
+            _ => 6 + 2 * ch,
+        };
+
+        tms * TM
+    }
+}
+
+impl Command for LegoPfCommand {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+
+    fn toggle(&self) -> Option<bool> {
+        Some(self.toggle)
+    }
+}
+
+impl AddressCommand for LegoPfCommand {
+    const ID: ProtocolId = ProtocolId::LegoPf;
+
+    fn raw(&self) -> u64 {
+        self.pack().into()
+    }
+
+    /// Address bit in bit 2 and the channel in bit 0 - 1
+    fn address(&self) -> u32 {
+        u32::from(self.address) << 2 | u32::from(self.channel)
+    }
+
+    /// Escape bit in bit 7, mode in bit 4 - 6 and data in bit 0 - 3
+    fn command(&self) -> u32 {
+        u32::from(self.escape) << 7 | u32::from(self.mode) << 4 | u32::from(self.data)
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        let cmd: u8 = cmd.try_into().ok()?;
+
+        if addr > 0x7 {
+            return None;
+        }
+
+        Some(Self {
+            toggle: false,
+            escape: cmd & 0x80 != 0,
+            channel: (addr & 0x3) as u8,
+            address: addr & 0x4 != 0,
+            mode: cmd >> 4 & 0x7,
+            data: cmd & 0xF,
+        })
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    cmd::AddressCommand,
+    protocol::{
+        lego_pf::{LegoPfCommand, MESSAGES},
+        LegoPf,
+    },
+    receiver::BufferInputReceiver,
+    sender::PulsedataBuffer,
+};
+
+/// Entries of a message, start bit, 16 bits and the stop mark
+const MSG_LEN: usize = 2 + 2 * 16 + 1;
+
+#[test]
+fn cmd_pack_unpack() {
+    // Channel 1, output A forward step 7
+    let cmd = LegoPfCommand::single_output(0, false, 7);
+    let bits = cmd.pack();
+
+    assert_eq!(bits, 0x047C);
+    assert_eq!(LegoPfCommand::unpack(bits), Some(cmd));
+
+    let cmd = LegoPfCommand {
+        toggle: true,
+        escape: true,
+        channel: 3,
+        address: true,
+        mode: 0b101,
+        data: 0xA,
+    };
+    assert_eq!(cmd.pack(), 0xFDA7);
+    assert_eq!(LegoPfCommand::unpack(cmd.pack()), Some(cmd));
+
+    // LRC
+    assert_eq!(LegoPfCommand::unpack(bits ^ 1), None);
+    assert_eq!(LegoPfCommand::unpack(bits ^ 1 << 12), None);
+}
+
+#[test]
+fn cmd_create() {
+    let cmd = LegoPfCommand::create(0b110, 0xD5).unwrap();
+    assert_eq!(
+        cmd,
+        LegoPfCommand {
+            toggle: false,
+            escape: true,
+            channel: 2,
+            address: true,
+            mode: 0b101,
+            data: 5,
+        }
+    );
+    assert_eq!(cmd.address(), 0b110);
+    assert_eq!(cmd.command(), 0xD5);
+
+    assert_eq!(LegoPfCommand::create(0x8, 0), None);
+    assert_eq!(LegoPfCommand::create(0, 0x100), None);
+}
+
+#[test]
+fn encode_burst() {
+    for channel in 0..4 {
+        let mut ptb = PulsedataBuffer::<192>::new();
+        let cmd = LegoPfCommand::single_output(channel, true, 9);
+        ptb.load::<LegoPf, 1_000_000>(&cmd);
+
+        let buf = ptb.buffer();
+        assert_eq!(buf.len(), 1 + MESSAGES * (MSG_LEN + 1) - 1);
+
+        let ch = u32::from(channel) + 1;
+        // Delay before the first message
+        assert_eq!(buf[0], (4 - ch) * 16_000);
+        // Start bit
+        assert_eq!(&buf[1..3], &[158, 1026]);
+
+        // Start of one message to the start of the next
+        let intervals = [5, 5, 6 + 2 * ch, 6 + 2 * ch];
+        for (n, tms) in intervals.iter().enumerate() {
+            let start = 1 + n * (MSG_LEN + 1);
+            let msg = &buf[start..start + MSG_LEN + 1];
+            assert_eq!(msg.iter().sum::<u32>(), tms * 16_000);
+        }
+
+        // Every message is decoded
+        let mut brecv = BufferInputReceiver::<LegoPf>::with_frequenzy(1_000_000);
+        let cmds = brecv.iter(buf).collect::<std::vec::Vec<_>>();
+        assert_eq!(cmds, std::vec![cmd; MESSAGES]);
+    }
+}
+
+#[test]
+fn encode_small_buffer() {
+    let cmd = LegoPfCommand::single_output(0, false, 3);
+
+    let mut ptb = PulsedataBuffer::<179>::new();
+    ptb.load::<LegoPf, 1_000_000>(&cmd);
+    assert!(ptb.buffer().is_empty());
+
+    let mut ptb = PulsedataBuffer::<180>::new();
+    ptb.load::<LegoPf, 1_000_000>(&cmd);
+    assert_eq!(ptb.buffer().len(), 180);
+}
+
+#[test]
+fn all_commands() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<192>::new();
+
+    for bits in 0..=0xFFF {
+        let n1 = (bits >> 8) as u8;
+        let cmd = LegoPfCommand {
+            toggle: n1 & 0x8 != 0,
+            escape: n1 & 0x4 != 0,
+            channel: n1 & 0x3,
+            address: bits & 0x80 != 0,
+            mode: (bits >> 4 & 0x7) as u8,
+            data: (bits & 0xF) as u8,
+        };
+
+        ptb.reset();
+        ptb.load::<LegoPf, FREQ>(&cmd);
+
+        let mut brecv = BufferInputReceiver::<LegoPf>::with_frequenzy(FREQ);
+        assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+    }
+}
+
+#[test]
+fn bad_lrc() {
+    let mut ptb = PulsedataBuffer::<192>::new();
+    ptb.load::<LegoPf, 1_000_000>(&LegoPfCommand::single_output(0, false, 7));
+
+    // Flip the last bit of the first message, a zero
+    let mut buf = std::vec::Vec::from(&ptb.buffer()[..MSG_LEN + 1]);
+    buf[MSG_LEN - 1] = 553;
+
+    let mut brecv = BufferInputReceiver::<LegoPf>::with_frequenzy(1_000_000);
+    assert_eq!(brecv.iter(&buf).next(), None);
+}
+
+#[test]
+fn clock_frequencies() {
+    one_freq::<40_000>();
+    one_freq::<1_000_000>();
+    one_freq::<48_000_000>();
+}
+
+fn one_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<192>::new();
+    let cmd = LegoPfCommand::single_output(2, true, 0xB);
+    ptb.load::<LegoPf, F>(&cmd);
+
+    let mut brecv = BufferInputReceiver::<LegoPf>::with_frequenzy(F);
+
+    assert_eq!(brecv.iter(ptb.buffer()).count(), MESSAGES);
+}
@@ -1,4 +1,10 @@
 //! Nec protocol variant from Apple
+//!
+//! The 16 address bits holds the 5 bit command page and the 11 bit Apple vendor id.
+//! It's followed by an odd parity bit, the 7 bit command and the 8 bit device id. The device id
+//! is used for pairing a remote with a device.
+
+use core::convert::TryInto;
 
 use crate::{
     cmd::{AddressCommand, Command},
@@ -15,19 +21,31 @@ pub struct AppleNecCommand {
     pub repeat: bool,
 }
 
+const APPLE_VENDOR_ID: u16 = 0x43f;
+
+impl AppleNecCommand {
+    pub const fn new(command_page: u8, command: u8, device_id: u8) -> Self {
+        AppleNecCommand {
+            command_page,
+            command,
+            device_id,
+            repeat: false,
+        }
+    }
+}
+
 impl NecCommandVariant for AppleNecCommand {
     const PULSE_DISTANCE: &'static NecPulseLen = NEC_STANDARD_TIMING;
 
-    fn validate(bits: u32) -> bool {
+    fn validate(bits: u64) -> bool {
         let vendor = ((bits >> 5) & 0x7FF) as u16;
-        const APPLE_VENDOR_ID: u16 = 0x43f;
 
         vendor == APPLE_VENDOR_ID &&
             // Odd parity
             (bits.count_ones() & 0x1) == 1
     }
 
-    fn unpack(bits: u32, repeat: bool) -> Option<Self> {
+    fn unpack(bits: u64, repeat: bool) -> Option<Self> {
         if !Self::validate(bits) {
             return None;
         }
@@ -51,8 +69,16 @@ impl NecCommandVariant for AppleNecCommand {
         })
     }
 
-    fn pack(&self) -> u32 {
-        unimplemented!()
+    fn pack(&self) -> u64 {
+        let bits = u32::from(self.command_page & 0x1F)
+            | u32::from(APPLE_VENDOR_ID) << 5
+            | u32::from(self.command & 0x7F) << 17
+            | u32::from(self.device_id) << 24;
+
+        // Set the parity bit to make the number of ones odd
+        let parity_bit = !bits.count_ones() & 0x1;
+
+        u64::from(bits | parity_bit << 16)
     }
 }
 
@@ -65,15 +91,24 @@ impl Command for AppleNecCommand {
 impl AddressCommand for AppleNecCommand {
     const ID: ProtocolId = ProtocolId::NecApple;
 
+    /// The device id
     fn address(&self) -> u32 {
-        0
+        self.device_id.into()
     }
 
+    /// Command page in bit 7 - 11 and command in bit 0 - 6
     fn command(&self) -> u32 {
-        u32::from((self.command_page << 7) | self.command)
+        u32::from(self.command_page) << 7 | u32::from(self.command)
     }
 
-    fn create(_addr: u32, _cmd: u32) -> Option<Self> {
-        None
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        let device_id = addr.try_into().ok()?;
+        let command_page = (cmd >> 7).try_into().ok().filter(|page| *page <= 0x1F)?;
+
+        Some(AppleNecCommand::new(
+            command_page,
+            (cmd & 0x7F) as u8,
+            device_id,
+        ))
     }
 }
@@ -0,0 +1,63 @@
+//! NEC variant with 16 bit addresses and 16 bit commands, without any check bits
+
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::nec::{NecCommandVariant, NecPulseLen, NEC_STANDARD_TIMING},
+    ProtocolId,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Nec Command with 16 bit address and 16 bit command
+pub struct NecExtCommand {
+    pub addr: u16,
+    pub cmd: u16,
+    pub repeat: bool,
+}
+
+impl NecCommandVariant for NecExtCommand {
+    const PULSE_DISTANCE: &'static NecPulseLen = NEC_STANDARD_TIMING;
+
+    fn validate(_bits: u64) -> bool {
+        true
+    }
+
+    fn unpack(bits: u64, repeat: bool) -> Option<Self> {
+        let addr = (bits & 0xFFFF) as u16;
+        let cmd = ((bits >> 16) & 0xFFFF) as u16;
+
+        Some(NecExtCommand { addr, cmd, repeat })
+    }
+
+    fn pack(&self) -> u64 {
+        u64::from(self.addr) | u64::from(self.cmd) << 16
+    }
+}
+
+impl Command for NecExtCommand {
+    fn is_repeat(&self) -> bool {
+        self.repeat
+    }
+}
+
+impl AddressCommand for NecExtCommand {
+    const ID: ProtocolId = ProtocolId::NecExt;
+
+    fn address(&self) -> u32 {
+        self.addr.into()
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        Some(NecExtCommand {
+            addr: addr.try_into().ok()?,
+            cmd: cmd.try_into().ok()?,
+            repeat: false,
+        })
+    }
+}
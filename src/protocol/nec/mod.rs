@@ -4,22 +4,30 @@ pub mod decoder;
 pub mod encoder;
 
 mod apple;
+mod ext;
 mod nec16;
+mod nec42;
 mod raw;
 mod samsung;
 mod standard;
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests;
 
 use core::marker::PhantomData;
 
 pub use apple::AppleNecCommand;
+pub use ext::NecExtCommand;
 pub use nec16::Nec16Command;
+pub use nec42::Nec42Command;
 pub use raw::NecDebugCmd;
 pub use samsung::SamsungNecCommand;
 pub use standard::NecCommand;
 
-use crate::protocol::Protocol;
+use crate::protocol::{
+    pulsedistance::{BitOrder, PulseDistance, PulseDistanceProtocol, Repeat},
+    Protocol,
+};
 
 /// Nec Receiver with Nec standard bit encoding and Standard timing
 pub struct Nec<C: NecCommandVariant = NecCommand> {
@@ -37,6 +45,12 @@ pub type SamsungNec = Nec<SamsungNecCommand>;
 /// Nec variant with 16 bit address and Nec standard timing
 pub type Nec16 = Nec<Nec16Command>;
 
+/// Nec variant with 16 bit address, 16 bit command and Nec standard timing
+pub type NecExt = Nec<NecExtCommand>;
+
+/// Nec variant with 13 bit address and 42 bit frames
+pub type Nec42 = Nec<Nec42Command>;
+
 /// Nec variant with Apple specific bit encoding and Standard timing
 pub type AppleNec = Nec<AppleNecCommand>;
 
@@ -45,49 +59,70 @@ pub type NecDebug = Nec<NecDebugCmd>;
 
 /// Nec Command Variant
 pub trait NecCommandVariant: Sized {
+    /// Timing and frame layout. The decoder receives `PULSE_DISTANCE.nbits` bits per frame
     const PULSE_DISTANCE: &'static NecPulseLen;
 
     /// Validate the bits as a Command of this type
-    fn validate(bits: u32) -> bool;
+    fn validate(bits: u64) -> bool;
 
     /// Unpack the bits into Command
-    fn unpack(bits: u32, repeat: bool) -> Option<Self>;
+    fn unpack(bits: u64, repeat: bool) -> Option<Self>;
+
+    /// Pack command into bits
+    fn pack(&self) -> u64;
+}
 
-    /// Pack command into a u32
-    fn pack(&self) -> u32;
+impl<C: NecCommandVariant> PulseDistanceProtocol for Nec<C> {
+    const DESCRIPTOR: &'static PulseDistance = C::PULSE_DISTANCE;
+
+    fn unpack(bits: u64, repeat: bool) -> Option<C> {
+        if !C::validate(bits) {
+            return None;
+        }
+        C::unpack(bits, repeat)
+    }
+
+    fn pack(cmd: &C) -> u64 {
+        cmd.pack()
+    }
 }
 
 const NEC_STANDARD_TIMING: &NecPulseLen = &NecPulseLen {
     header_high: 9000,
     header_low: 4500,
-    repeat_low: 2250,
     data_high: 560,
-    data_zero_low: 560,
-    data_one_low: 1690,
+    zero_low: 560,
+    one_low: 1690,
+    nbits: 32,
+    order: BitOrder::Lsb,
+    stop_bit: true,
+    repeat: Repeat::Header {
+        high: 9000,
+        low: 2250,
+        period: 108_000,
+    },
 };
 
 const NEC_SAMSUNG_TIMING: &NecPulseLen = &NecPulseLen {
     header_high: 4500,
     header_low: 4500,
-    repeat_low: 2250,
-    data_zero_low: 560,
     data_high: 560,
-    data_one_low: 1690,
+    zero_low: 560,
+    one_low: 1690,
+    nbits: 32,
+    order: BitOrder::Lsb,
+    stop_bit: true,
+    repeat: Repeat::Header {
+        high: 4500,
+        low: 2250,
+        period: 108_000,
+    },
+};
+
+const NEC42_TIMING: &NecPulseLen = &NecPulseLen {
+    nbits: 42,
+    ..*NEC_STANDARD_TIMING
 };
 
 /// High and low times for Nec-like protocol. In us.
-#[derive(Copy, Clone)]
-pub struct NecPulseLen {
-    /// Header high
-    header_high: u32,
-    /// Header low
-    header_low: u32,
-    /// Repeat low
-    repeat_low: u32,
-    /// Data high
-    data_high: u32,
-    /// Zero low
-    data_zero_low: u32,
-    /// One low
-    data_one_low: u32,
-}
+pub type NecPulseLen = PulseDistance;
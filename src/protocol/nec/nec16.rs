@@ -1,6 +1,12 @@
 //! NEC variant with 16 bit addresses and 8 bit data
 
-use crate::protocol::nec::{NecCommandVariant, NecPulseLen, NEC_STANDARD_TIMING};
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::nec::{NecCommandVariant, NecPulseLen, NEC_STANDARD_TIMING},
+    ProtocolId,
+};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -14,20 +20,46 @@ pub struct Nec16Command {
 impl NecCommandVariant for Nec16Command {
     const PULSE_DISTANCE: &'static NecPulseLen = NEC_STANDARD_TIMING;
 
-    fn validate(bits: u32) -> bool {
+    fn validate(bits: u64) -> bool {
         ((bits >> 24) ^ (bits >> 16)) & 0xFF == 0xFF
     }
 
-    fn unpack(bits: u32, repeat: bool) -> Option<Self> {
+    fn unpack(bits: u64, repeat: bool) -> Option<Self> {
         let addr = (bits & 0xFFFF) as u16;
         let cmd = ((bits >> 16) & 0xFF) as u8;
 
         Some(Nec16Command { addr, cmd, repeat })
     }
 
-    fn pack(&self) -> u32 {
-        let addr = u32::from(self.addr);
-        let cmd = (u32::from(self.cmd) << 16) | (u32::from(!self.cmd) << 24);
+    fn pack(&self) -> u64 {
+        let addr = u64::from(self.addr);
+        let cmd = (u64::from(self.cmd) << 16) | (u64::from(!self.cmd) << 24);
         addr | cmd
     }
 }
+
+impl Command for Nec16Command {
+    fn is_repeat(&self) -> bool {
+        self.repeat
+    }
+}
+
+impl AddressCommand for Nec16Command {
+    const ID: ProtocolId = ProtocolId::Nec16;
+
+    fn address(&self) -> u32 {
+        self.addr.into()
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        Some(Nec16Command {
+            addr: addr.try_into().ok()?,
+            cmd: cmd.try_into().ok()?,
+            repeat: false,
+        })
+    }
+}
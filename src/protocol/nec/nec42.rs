@@ -0,0 +1,79 @@
+//! NEC42, as used by Toshiba and Onkyo. 13 bit address and 8 bit command,
+//! both followed by their complement, 42 bits in total
+
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::nec::{NecCommandVariant, NecPulseLen, NEC42_TIMING},
+    ProtocolId,
+};
+
+const ADDR_MASK: u64 = 0x1FFF;
+const CMD_MASK: u64 = 0xFF;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Nec42 Command with 13 bit address
+pub struct Nec42Command {
+    pub addr: u16,
+    pub cmd: u8,
+    pub repeat: bool,
+}
+
+impl NecCommandVariant for Nec42Command {
+    const PULSE_DISTANCE: &'static NecPulseLen = NEC42_TIMING;
+
+    fn validate(bits: u64) -> bool {
+        ((bits >> 13) ^ bits) & ADDR_MASK == ADDR_MASK
+            && ((bits >> 34) ^ (bits >> 26)) & CMD_MASK == CMD_MASK
+    }
+
+    fn unpack(bits: u64, repeat: bool) -> Option<Self> {
+        if !Self::validate(bits) {
+            return None;
+        }
+
+        let addr = (bits & ADDR_MASK) as u16;
+        let cmd = ((bits >> 26) & CMD_MASK) as u8;
+
+        Some(Nec42Command { addr, cmd, repeat })
+    }
+
+    fn pack(&self) -> u64 {
+        let addr = u64::from(self.addr) & ADDR_MASK;
+        let cmd = u64::from(self.cmd);
+
+        addr | (!addr & ADDR_MASK) << 13 | cmd << 26 | (!cmd & CMD_MASK) << 34
+    }
+}
+
+impl Command for Nec42Command {
+    fn is_repeat(&self) -> bool {
+        self.repeat
+    }
+}
+
+impl AddressCommand for Nec42Command {
+    const ID: ProtocolId = ProtocolId::Nec42;
+
+    fn address(&self) -> u32 {
+        self.addr.into()
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        if u64::from(addr) > ADDR_MASK {
+            return None;
+        }
+
+        Some(Nec42Command {
+            addr: addr as u16,
+            cmd: cmd.try_into().ok()?,
+            repeat: false,
+        })
+    }
+}
@@ -12,15 +12,15 @@ pub struct NecDebugCmd {
 impl NecCommandVariant for NecDebugCmd {
     const PULSE_DISTANCE: &'static NecPulseLen = NEC_STANDARD_TIMING;
 
-    fn validate(_bits: u32) -> bool {
+    fn validate(_bits: u64) -> bool {
         true
     }
 
-    fn unpack(bits: u32, _repeat: bool) -> Option<Self> {
-        Some(NecDebugCmd { bits })
+    fn unpack(bits: u64, _repeat: bool) -> Option<Self> {
+        Some(NecDebugCmd { bits: bits as u32 })
     }
 
-    fn pack(&self) -> u32 {
-        self.bits
+    fn pack(&self) -> u64 {
+        self.bits.into()
     }
 }
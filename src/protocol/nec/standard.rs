@@ -17,20 +17,20 @@ pub struct NecCommand {
 impl NecCommandVariant for NecCommand {
     const PULSE_DISTANCE: &'static NecPulseLen = NEC_STANDARD_TIMING;
 
-    fn validate(bits: u32) -> bool {
+    fn validate(bits: u64) -> bool {
         ((bits >> 24) ^ (bits >> 16)) & 0xFF == 0xFF && ((bits >> 8) ^ bits) & 0xFF == 0xFF
     }
 
-    fn unpack(bits: u32, repeat: bool) -> Option<Self> {
+    fn unpack(bits: u64, repeat: bool) -> Option<Self> {
         let addr = ((bits) & 0xFF) as u8;
         let cmd = ((bits >> 16) & 0xFF) as u8;
 
         Some(NecCommand { addr, cmd, repeat })
     }
 
-    fn pack(&self) -> u32 {
-        let addr = u32::from(self.addr) | ((u32::from(!self.addr) & 0xFF) << 8);
-        let cmd = (u32::from(self.cmd) << 16) | (u32::from(!self.cmd) << 24);
+    fn pack(&self) -> u64 {
+        let addr = u64::from(self.addr) | ((u64::from(!self.addr) & 0xFF) << 8);
+        let cmd = (u64::from(self.cmd) << 16) | (u64::from(!self.cmd) << 24);
         addr | cmd
     }
 }
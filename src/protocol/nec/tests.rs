@@ -1,9 +1,13 @@
 use fugit::{TimerDurationU32, TimerInstantU32};
 
 use crate::{
+    cmd::AddressCommand,
     protocol::{
-        nec::{AppleNecCommand, Nec16Command, NecCommand, NecCommandVariant, SamsungNecCommand},
-        Nec,
+        nec::{
+            AppleNecCommand, Nec16Command, Nec42Command, NecCommand, NecCommandVariant,
+            NecExtCommand, SamsungNecCommand,
+        },
+        AppleNec, Nec, Nec42, NecExt,
     },
     receiver::BufferInputReceiver,
     sender::PulsedataBuffer,
@@ -94,6 +98,82 @@ fn cmd_nec16() {
     assert_eq!(cmd, cmd2);
 }
 
+#[test]
+fn cmd_nec_ext() {
+    let cmd = NecExtCommand {
+        addr: 0x1234,
+        cmd: 0xBEEF,
+        repeat: false,
+    };
+    let bits = cmd.pack();
+
+    assert!(NecExtCommand::validate(bits));
+    assert_eq!(bits, 0xBEEF1234);
+    assert_eq!(NecExtCommand::unpack(bits, false), Some(cmd));
+
+    assert_eq!(NecExtCommand::create(0x1234, 0xBEEF), Some(cmd));
+    assert_eq!(NecExtCommand::create(0x10000, 0), None);
+}
+
+#[test]
+fn cmd_nec42() {
+    let cmd = Nec42Command {
+        addr: 0x1ABC,
+        cmd: 0x5A,
+        repeat: false,
+    };
+    let bits = cmd.pack();
+
+    assert!(Nec42Command::validate(bits));
+    assert_eq!(bits >> 42, 0);
+    assert_eq!(bits, 0x295_68A8_7ABC);
+    assert_eq!(Nec42Command::unpack(bits, false), Some(cmd));
+
+    // Broken complement
+    assert!(!Nec42Command::validate(bits ^ 1 << 20));
+    assert_eq!(Nec42Command::unpack(bits ^ 1 << 40, false), None);
+
+    assert_eq!(Nec42Command::create(0x1ABC, 0x5A), Some(cmd));
+    assert_eq!(Nec42Command::create(0x2000, 0), None);
+    assert_eq!(Nec42Command::create(0, 0x100), None);
+}
+
+#[test]
+fn nec_ext_and_nec42_roundtrip() {
+    const FREQUENCY: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<96>::new();
+
+    for (addr, cmdnum) in [(0, 0), (0x1234, 0xBEEF), (0xFFFF, 0x00FF), (0x00FF, 0xFFFF)] {
+        let cmd = NecExtCommand {
+            addr,
+            cmd: cmdnum,
+            repeat: false,
+        };
+        ptb.reset();
+        ptb.load::<NecExt, FREQUENCY>(&cmd);
+
+        let mut brecv = BufferInputReceiver::<NecExt>::with_frequenzy(FREQUENCY);
+        assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+    }
+
+    for addr in [0, 0x0FFF, 0x1ABC, 0x1FFF] {
+        for cmdnum in 0..=255 {
+            let cmd = Nec42Command {
+                addr,
+                cmd: cmdnum,
+                repeat: false,
+            };
+            ptb.reset();
+            ptb.load::<Nec42, FREQUENCY>(&cmd);
+            // Header, 42 bits and the stop bit
+            assert_eq!(ptb.buffer().len(), 1 + 2 + 2 * 42 + 1);
+
+            let mut brecv = BufferInputReceiver::<Nec42>::with_frequenzy(FREQUENCY);
+            assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+        }
+    }
+}
+
 #[test]
 fn all_nec_commands() {
     const FREQUENCY: u32 = 40_000;
@@ -146,7 +226,7 @@ fn one_freq<const F: u32>() {
 
 #[test]
 fn cmd_apple2009() {
-    let tests: &[(u32, u8)] = &[
+    let tests: &[(u64, u8)] = &[
         (0x9B0987EE, 0x04), // Left
         (0x9B0A87EE, 0x05), // Up
         (0x9B0687EE, 0x03), // Right
@@ -165,7 +245,48 @@ fn cmd_apple2009() {
 
         assert_eq!(cmd.command_page, 0xE);
         assert_eq!(cmd.command, *cmdnum);
+        assert_eq!(cmd.device_id, 0x9B);
+
+        assert_eq!(cmd.pack(), *bits);
+    }
+}
+
+#[test]
+fn cmd_apple_create() {
+    let cmd = AppleNecCommand::create(0x9B, 0xE << 7 | 0x05).unwrap();
+
+    assert_eq!(cmd, AppleNecCommand::new(0xE, 0x05, 0x9B));
+    assert_eq!(cmd.address(), 0x9B);
+    assert_eq!(cmd.command(), 0xE << 7 | 0x05);
+    assert_eq!(cmd.pack(), 0x9B0A87EE);
+
+    assert_eq!(AppleNecCommand::create(0x100, 0x05), None);
+    assert_eq!(AppleNecCommand::create(0x9B, 0x20 << 7), None);
+}
+
+#[test]
+fn apple_remote() {
+    use crate::remotecontrol::{nec::Apple2009, Action, RemoteControlModel};
+
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<96>::new();
+
+    for device_id in [0x00, 0x9B, 0xFF] {
+        for action in [Action::Menu, Action::Up, Action::Enter, Action::Play_Pause2] {
+            ptb.reset();
+            let cmd = Apple2009::encode_with_id(&action, device_id).unwrap();
+            ptb.load::<AppleNec, FREQ>(&cmd);
+
+            let mut brecv = BufferInputReceiver::<AppleNec>::with_frequenzy(FREQ);
+            let cmdres = brecv.iter(ptb.buffer()).next().unwrap();
+
+            assert_eq!(cmdres.device_id, device_id);
+            assert_eq!(Apple2009::decode(&cmdres), Some(action));
+        }
     }
+
+    let cmd = Apple2009::encode(&Action::Down).unwrap();
+    assert_eq!((cmd.command_page, cmd.command), (0xE, 0x06));
 }
 
 #[test]
@@ -245,3 +366,82 @@ fn fugit() {
     assert_eq!(cmds[1].repeat, true);
     assert_eq!(cmds[7].repeat, true);
 }
+
+#[test]
+fn orphan_repeat() {
+    use crate::receiver::{DecoderBuilder, DecodingError, ProtocolDecoder, State};
+
+    const FREQ: u32 = 1_000_000;
+    const REPEAT: [u32; 4] = [0, 9000, 2250, 560];
+
+    let frame = NecCommand {
+        addr: 7,
+        cmd: 44,
+        repeat: false,
+    };
+    let repeat = NecCommand {
+        repeat: true,
+        ..frame
+    };
+
+    // Repeat without a command
+    let mut decoder = <Nec as DecoderBuilder<u32>>::build(FREQ);
+    let mut state = State::Idle;
+    for (i, dt) in REPEAT.iter().enumerate() {
+        state = decoder.event(i % 2 == 0, *dt);
+    }
+    assert_eq!(state, State::Error(DecodingError::OrphanRepeat));
+    assert_eq!(decoder.command(), None);
+
+    // Repeats in the repeat window, and one after it
+    let mut ptb = PulsedataBuffer::<96>::new();
+    ptb.load::<Nec, FREQ>(&frame);
+
+    let mut buf = std::vec::Vec::from(ptb.buffer());
+    for gap in [40_000, 97_000, 150_000] {
+        buf.push(gap);
+        buf.extend_from_slice(&REPEAT[1..]);
+    }
+
+    let mut receiver = BufferInputReceiver::<Nec>::with_frequenzy(FREQ);
+    let cmds = receiver.iter(&buf).collect::<std::vec::Vec<_>>();
+
+    assert_eq!(cmds, [frame, repeat, repeat]);
+
+    // The period is from the start of the frame, the gap after the 68 ms frame is at most 53 ms
+    for (gap, cmds) in [(50_000, &[frame, repeat][..]), (56_000, &[frame][..])] {
+        let mut buf = std::vec::Vec::from(ptb.buffer());
+        buf.push(gap);
+        buf.extend_from_slice(&REPEAT[1..]);
+
+        let mut receiver = BufferInputReceiver::<Nec>::with_frequenzy(FREQ);
+        assert_eq!(receiver.iter(&buf).collect::<std::vec::Vec<_>>(), cmds);
+    }
+}
+
+#[test]
+fn check_bits() {
+    use crate::receiver::{DecoderBuilder, DecodingError, ProtocolDecoder, State};
+
+    let mut ptb = PulsedataBuffer::<96>::new();
+    ptb.load::<Nec, 1_000_000>(&NecCommand {
+        addr: 7,
+        cmd: 44,
+        repeat: false,
+    });
+
+    // A one in the inverted command is received as a zero
+    let mut buf = std::vec::Vec::from(ptb.buffer());
+    let bit = 3 + 2 * 24 + 1;
+    assert_eq!(buf[bit], 1690);
+    buf[bit] = 560;
+
+    let mut decoder = <Nec as DecoderBuilder<u32>>::build(1_000_000);
+    let mut state = State::Idle;
+    for (i, dt) in buf.iter().enumerate() {
+        state = decoder.event(i % 2 == 0, *dt);
+    }
+
+    assert_eq!(state, State::Error(DecodingError::Validation));
+    assert_eq!(decoder.command(), None);
+}
@@ -0,0 +1,17 @@
+//! Nrc17 decoder. Nrc17 is decoded by the generic bi-phase decoder
+
+use crate::{
+    protocol::{biphase::BiPhaseDecoder, Nrc17},
+    receiver::{time::InfraMonotonic, DecoderBuilder},
+};
+
+/// Nrc17 decoder
+pub type Nrc17Decoder<Mono> = BiPhaseDecoder<Mono, Nrc17>;
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Nrc17 {
+    type Decoder = Nrc17Decoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        BiPhaseDecoder::new(freq)
+    }
+}
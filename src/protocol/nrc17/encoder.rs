@@ -0,0 +1,16 @@
+use crate::{
+    protocol::{
+        biphase::encoder::{encode, encoder_data},
+        Nrc17,
+    },
+    sender::ProtocolEncoder,
+};
+
+impl<const FREQ: u32> ProtocolEncoder<FREQ> for Nrc17 {
+    type EncoderData = [u32; 1];
+    const DATA: Self::EncoderData = encoder_data(&super::NRC17_BIPHASE, FREQ);
+
+    fn encode(cmd: &Self::Cmd, buf: &mut [u32]) -> usize {
+        encode::<Self>(&<Self as ProtocolEncoder<FREQ>>::DATA, cmd, buf)
+    }
+}
@@ -0,0 +1,132 @@
+//! Nokia NRC17
+//!
+//! Bi-phase coded protocol with a 500 us unit, used by Nokia and ITT set-top boxes and TVs.
+//! A frame is a 500 us pre-pulse, a 2.5 ms pause and a start bit, always one, followed by the
+//! 8 bit command, the 4 bit address and the 4 bit sub-code, LSB first. A one is a mark followed
+//! by a space.
+//!
+//! The remote sends a start frame when a button is pressed and a stop frame when it is
+//! released, both with the command 0xFE and the address 0xF.
+
+use core::convert::TryInto;
+
+pub mod decoder;
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::{
+        biphase::{BiPhase, BiPhaseProtocol, Level, Polarity},
+        Protocol,
+    },
+    ProtocolId,
+};
+
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+/// Pre-pulse, pause and the start bit
+const NRC17_BIPHASE: BiPhase = BiPhase {
+    unit: 500,
+    header: &[
+        Level::Mark(1),
+        Level::Space(5),
+        Level::Mark(1),
+        Level::Space(1),
+    ],
+    nbits: 16,
+    polarity: Polarity::MarkSpace,
+    double_width: 0,
+};
+
+/// Nokia NRC17
+pub struct Nrc17;
+
+impl Protocol for Nrc17 {
+    type Cmd = Nrc17Command;
+}
+
+impl BiPhaseProtocol for Nrc17 {
+    const DESCRIPTOR: &'static BiPhase = &NRC17_BIPHASE;
+
+    fn unpack(bits: u64, _nbits: u8) -> Option<Nrc17Command> {
+        // Sent LSB first
+        Some(Nrc17Command::unpack((bits as u16).reverse_bits()))
+    }
+
+    fn pack(cmd: &Nrc17Command) -> (u64, u8) {
+        (cmd.pack().reverse_bits().into(), 16)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Nrc17Command {
+    /// Address, 4 bits
+    pub addr: u8,
+    /// Sub-code, 4 bits
+    pub subcode: u8,
+    pub cmd: u8,
+}
+
+impl Nrc17Command {
+    /// Command of the start and stop frames
+    pub const START_STOP_CMD: u8 = 0xFE;
+
+    pub const fn new(addr: u8, subcode: u8, cmd: u8) -> Self {
+        Self { addr, subcode, cmd }
+    }
+
+    /// The frame sent when a button is pressed and released
+    pub const fn start_stop() -> Self {
+        Self::new(0xF, 0xF, Self::START_STOP_CMD)
+    }
+
+    /// Is this a start or stop frame
+    pub const fn is_start_stop(&self) -> bool {
+        self.cmd == Self::START_STOP_CMD && self.addr == 0xF
+    }
+
+    /// Unpack the command in bit 0 - 7, the address in bit 8 - 11 and the sub-code in bit 12 - 15
+    pub const fn unpack(bits: u16) -> Self {
+        Self {
+            cmd: bits as u8,
+            addr: ((bits >> 8) & 0xF) as u8,
+            subcode: (bits >> 12) as u8,
+        }
+    }
+
+    pub fn pack(&self) -> u16 {
+        u16::from(self.cmd) | u16::from(self.addr & 0xF) << 8 | u16::from(self.subcode & 0xF) << 12
+    }
+}
+
+impl Command for Nrc17Command {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+}
+
+impl AddressCommand for Nrc17Command {
+    const ID: ProtocolId = ProtocolId::Nrc17;
+
+    fn raw(&self) -> u64 {
+        self.pack().into()
+    }
+
+    /// Address in bit 0 - 3 and sub-code in bit 4 - 7
+    fn address(&self) -> u32 {
+        u32::from(self.subcode) << 4 | u32::from(self.addr)
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Nrc17Command> {
+        let addr: u8 = addr.try_into().ok()?;
+        let cmd = cmd.try_into().ok()?;
+
+        Some(Nrc17Command::new(addr & 0xF, addr >> 4, cmd))
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{
+    cmd::AddressCommand,
+    protocol::{nrc17::Nrc17Command, Nrc17},
+    receiver::BufferInputReceiver,
+    sender::PulsedataBuffer,
+};
+
+#[test]
+fn nrc17_command() {
+    let cmd = Nrc17Command::new(0x3, 0x9, 0x41);
+    assert_eq!(cmd.pack(), 0x9341);
+    assert_eq!(Nrc17Command::unpack(cmd.pack()), cmd);
+
+    assert_eq!(cmd.address(), 0x93);
+    assert_eq!(Nrc17Command::create(0x93, 0x41), Some(cmd));
+    assert_eq!(Nrc17Command::create(0x100, 0x41), None);
+
+    assert!(Nrc17Command::start_stop().is_start_stop());
+    assert!(!cmd.is_start_stop());
+}
+
+#[test]
+fn encode_frame() {
+    let mut ptb = PulsedataBuffer::<64>::new();
+    ptb.load::<Nrc17, 1_000_000>(&Nrc17Command::new(0, 0, 0x01));
+
+    // Pre-pulse, pause, start bit and the first command bit, a one
+    assert_eq!(&ptb.buffer()[..5], &[0, 500, 2500, 500, 500]);
+}
+
+#[test]
+fn all_commands() {
+    const SAMPLERATE: u32 = 40_000;
+
+    let mut ptb = PulsedataBuffer::<64>::new();
+
+    for address in 0..16 {
+        for subcode in [0, 1, 0xA, 0xF] {
+            for cmdnum in 0..=255 {
+                ptb.reset();
+
+                let cmd = Nrc17Command::new(address, subcode, cmdnum);
+                ptb.load::<Nrc17, SAMPLERATE>(&cmd);
+
+                let mut r = BufferInputReceiver::<Nrc17>::with_frequenzy(SAMPLERATE);
+                let cmdres = r.iter(ptb.buffer()).next();
+
+                assert_eq!(cmdres, Some(cmd));
+            }
+        }
+    }
+}
+
+#[test]
+fn timer_resolution() {
+    test_freq::<20_000>();
+    test_freq::<40_000>();
+    test_freq::<48_000_000>();
+}
+
+fn test_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<64>::new();
+    let cmd = Nrc17Command::new(0x5, 0x2, 0xB4);
+    ptb.load::<Nrc17, F>(&cmd);
+
+    let mut r = BufferInputReceiver::<Nrc17>::with_frequenzy(F);
+
+    assert_eq!(r.iter(ptb.buffer()).next(), Some(cmd));
+}
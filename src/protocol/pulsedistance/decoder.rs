@@ -0,0 +1,231 @@
+use core::marker::PhantomData;
+
+use crate::{
+    protocol::pulsedistance::{PulseDistance, PulseDistanceProtocol, Repeat},
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecodingError, ProtocolDecoder, State,
+    },
+};
+
+/// Index of the repeat window span
+const WINDOW: usize = 4;
+
+/// Lengths of the mark and space pairs, and half of the repeat window
+const fn pulselens(pd: &PulseDistance) -> [u32; 8] {
+    let (repeat, period) = match pd.repeat {
+        Repeat::Frame => (0, 0),
+        Repeat::Header { high, low, period } => (high + low, period),
+    };
+
+    [
+        pd.header_high + pd.header_low,
+        repeat,
+        pd.data_high + pd.zero_low,
+        pd.data_high + pd.one_low,
+        // With 100 % tolerance the span is 0 to period + 10 %. The window is checked at the end
+        // of the repeat header, the period is from the start of the frame to its start
+        (period + period / 10 + repeat) / 2,
+        0,
+        0,
+        0,
+    ]
+}
+
+const TOL: [u32; 8] = [7, 7, 25, 12, 100, 0, 0, 0];
+
+/// Decoder for protocols described by a [`PulseDistance`] table
+pub struct PulseDistanceDecoder<Mono: InfraMonotonic, Proto> {
+    // State
+    state: PulseDistanceState,
+    // Data buffer
+    bitbuf: u64,
+    // Protocol
+    proto: PhantomData<Proto>,
+    // Saved dt
+    dt_save: Mono::Duration,
+    // Bits of the last frame that unpacked into a command
+    last: Option<u64>,
+    // Time since the start of the last frame, or repeat
+    elapsed: Mono::Duration,
+
+    pulsespans: PulseSpans<Mono>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+// Internal receiver state
+pub enum PulseDistanceState {
+    // Waiting for first pulse
+    Init,
+    // Receiving data
+    Receiving(u8),
+    // Command received
+    Done,
+    // Repeat command received
+    RepeatDone,
+    // In error state
+    Err(DecodingError),
+}
+
+impl<Mono: InfraMonotonic, Proto: PulseDistanceProtocol> PulseDistanceDecoder<Mono, Proto> {
+    /// Create a decoder for a sample rate of `freq`
+    pub fn new(freq: u32) -> Self {
+        PulseDistanceDecoder {
+            state: PulseDistanceState::Init,
+            bitbuf: 0,
+            proto: PhantomData,
+            dt_save: Mono::ZERO_DURATION,
+            last: None,
+            elapsed: Mono::ZERO_DURATION,
+            pulsespans: PulseSpans::new(freq, &pulselens(Proto::DESCRIPTOR), &TOL),
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic, Proto: PulseDistanceProtocol> PulseDistanceDecoder<Mono, Proto> {
+    /// Header of `len` received, start timing the frame
+    fn header(&mut self, len: Mono::Duration) -> PulseDistanceState {
+        self.bitbuf = 0;
+        self.elapsed = len;
+        PulseDistanceState::Receiving(0)
+    }
+
+    /// Full frame received. Remember it if it is a command
+    fn done(&mut self) -> PulseDistanceState {
+        self.last = Proto::unpack(self.bitbuf, false).map(|_| self.bitbuf);
+
+        if self.last.is_some() {
+            PulseDistanceState::Done
+        } else {
+            PulseDistanceState::Err(DecodingError::Validation)
+        }
+    }
+
+    /// Repeat header of `len` received. Only valid in the repeat window of a command
+    fn repeat(&mut self, len: Mono::Duration) -> PulseDistanceState {
+        if self.last.is_some() {
+            self.elapsed = len;
+            PulseDistanceState::RepeatDone
+        } else {
+            PulseDistanceState::Err(DecodingError::OrphanRepeat)
+        }
+    }
+}
+
+impl From<PulseDistanceState> for State {
+    fn from(ns: PulseDistanceState) -> Self {
+        use PulseDistanceState::*;
+        match ns {
+            Init => State::Idle,
+            Done | RepeatDone => State::Done,
+            Err(e) => State::Error(e),
+            _ => State::Receiving,
+        }
+    }
+}
+
+impl<Mono, Proto> ProtocolDecoder<Proto, Mono> for PulseDistanceDecoder<Mono, Proto>
+where
+    Mono: InfraMonotonic,
+    Proto: PulseDistanceProtocol,
+{
+    #[rustfmt::skip]
+    fn event(&mut self, rising: bool, dur: Mono::Duration) -> State {
+
+        use PulseDistanceState::*;
+        use PulseWidth::*;
+
+        let pd = Proto::DESCRIPTOR;
+        let has_repeat = matches!(pd.repeat, super::Repeat::Header { .. });
+        let last = pd.nbits - 1;
+
+        if self.last.is_some() || matches!(self.state, Receiving(_)) {
+            self.elapsed = self.elapsed + dur;
+
+            // Outside the repeat window, forget the command
+            if !self.pulsespans.matches(WINDOW, self.elapsed) {
+                self.last = None;
+            }
+        }
+
+        if rising {
+
+            let total_duration = dur + self.dt_save;
+
+            let pulsewidth = self.pulsespans.get(total_duration)
+                .unwrap_or(PulseWidth::Invalid);
+
+            let status = match (self.state, pulsewidth) {
+                (Init,              Sync)   => self.header(total_duration),
+                (Init,              Repeat) if has_repeat => self.repeat(total_duration),
+                (Init,              _)      => Init,
+
+                (Receiving(n),      One) if n == last => { self.bitbuf |= 1 << pd.bit(n); self.done() }
+                (Receiving(n),      Zero) if n == last => self.done(),
+                (Receiving(n),      One)    => { self.bitbuf |= 1 << pd.bit(n); Receiving(n + 1) }
+                (Receiving(n),      Zero)   => Receiving(n + 1),
+                (Receiving(_),      _)      => Err(DecodingError::Data),
+
+                (Done,              _)      => Done,
+                (RepeatDone,        _)      => RepeatDone,
+                (Err(err),          _)      => Err(err),
+            };
+
+            trace!(
+                "State(prev, new): ({:?}, {:?}) pulsewidth: {:?}",
+                self.state,
+                status,
+                pulsewidth
+            );
+
+            self.state = status;
+
+            self.dt_save = Mono::ZERO_DURATION;
+        } else {
+            // Save
+            self.dt_save = dur;
+        }
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<Proto::Cmd> {
+        match self.state {
+            PulseDistanceState::Done => Proto::unpack(self.bitbuf, false),
+            PulseDistanceState::RepeatDone => self.last.and_then(|bits| Proto::unpack(bits, true)),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = PulseDistanceState::Init;
+        self.dt_save = Mono::ZERO_DURATION;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.pulsespans
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PulseWidth {
+    Sync = 0,
+    Repeat = 1,
+    Zero = 2,
+    One = 3,
+    Invalid = 4,
+}
+
+impl From<usize> for PulseWidth {
+    fn from(v: usize) -> Self {
+        match v {
+            0 => PulseWidth::Sync,
+            1 => PulseWidth::Repeat,
+            2 => PulseWidth::Zero,
+            3 => PulseWidth::One,
+            _ => PulseWidth::Invalid,
+        }
+    }
+}
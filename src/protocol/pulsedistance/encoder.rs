@@ -0,0 +1,62 @@
+use crate::protocol::{
+    pulsedistance::{PulseDistance, PulseDistanceProtocol, Repeat},
+    utils::scale_with_samplerate,
+};
+
+/// Lengths of the pulses in ticks of a `freq` Hz clock, used as `ProtocolEncoder::DATA`
+pub const fn encoder_data(pd: &PulseDistance, freq: u32) -> [u32; 7] {
+    let (repeat_high, repeat_low) = match pd.repeat {
+        Repeat::Frame => (0, 0),
+        Repeat::Header { high, low, .. } => (high, low),
+    };
+
+    [
+        scale_with_samplerate(pd.header_high, freq),
+        scale_with_samplerate(pd.header_low, freq),
+        scale_with_samplerate(repeat_high, freq),
+        scale_with_samplerate(repeat_low, freq),
+        scale_with_samplerate(pd.data_high, freq),
+        scale_with_samplerate(pd.zero_low, freq),
+        scale_with_samplerate(pd.one_low, freq),
+    ]
+}
+
+/// Encode `cmd` into `b`, with the pulse lengths from [`encoder_data`]
+pub fn encode<Proto: PulseDistanceProtocol>(
+    data: &[u32; 7],
+    cmd: &Proto::Cmd,
+    b: &mut [u32],
+) -> usize {
+    let [header_high, header_low, repeat_high, repeat_low, data_high, zero_low, one_low] = *data;
+    let pd = Proto::DESCRIPTOR;
+
+    b[0] = 0;
+
+    if Proto::is_repeat(cmd) && matches!(pd.repeat, Repeat::Header { .. }) {
+        b[1] = repeat_high;
+        b[2] = repeat_low;
+        b[3] = data_high;
+        return 4;
+    }
+
+    b[1] = header_high;
+    b[2] = header_low;
+
+    let bits = Proto::pack(cmd);
+
+    let mut bi = 3;
+
+    for n in 0..pd.nbits {
+        let one = (bits >> pd.bit(n)) & 1 != 0;
+        b[bi] = data_high;
+        b[bi + 1] = if one { one_low } else { zero_low };
+        bi += 2;
+    }
+
+    if pd.stop_bit {
+        b[bi] = data_high;
+        bi += 1;
+    }
+
+    bi
+}
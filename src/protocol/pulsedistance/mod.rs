@@ -0,0 +1,162 @@
+//! Generic pulse distance protocol
+//!
+//! Decoder and encoder for protocols where the bits are coded in the length of the space after a
+//! fixed length mark. A protocol is added by describing the timing and the frame layout in a
+//! [`PulseDistance`] table, implementing [`PulseDistanceProtocol`] for it and using the
+//! [`PulseDistanceDecoder`] and [`encoder::encode`] for the decoder and encoder.
+//!
+//! ```
+//! use infrared::{
+//!     protocol::{
+//!         pulsedistance::{
+//!             encoder::{encode, encoder_data},
+//!             BitOrder, PulseDistance, PulseDistanceDecoder, PulseDistanceProtocol, Repeat,
+//!         },
+//!         Protocol,
+//!     },
+//!     receiver::{time::InfraMonotonic, DecoderBuilder},
+//!     sender::ProtocolEncoder,
+//! };
+//!
+//! /// 16 bit protocol, MSB first, repeats the full frame
+//! pub struct MyProtocol;
+//!
+//! impl Protocol for MyProtocol {
+//!     type Cmd = u16;
+//! }
+//!
+//! impl PulseDistanceProtocol for MyProtocol {
+//!     const DESCRIPTOR: &'static PulseDistance = &PulseDistance {
+//!         header_high: 3000,
+//!         header_low: 1500,
+//!         data_high: 500,
+//!         zero_low: 500,
+//!         one_low: 1500,
+//!         nbits: 16,
+//!         order: BitOrder::Msb,
+//!         stop_bit: true,
+//!         repeat: Repeat::Frame,
+//!     };
+//!
+//!     fn unpack(bits: u64, _repeat: bool) -> Option<u16> {
+//!         Some(bits as u16)
+//!     }
+//!
+//!     fn pack(cmd: &u16) -> u64 {
+//!         u64::from(*cmd)
+//!     }
+//! }
+//!
+//! impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for MyProtocol {
+//!     type Decoder = PulseDistanceDecoder<Mono, MyProtocol>;
+//!
+//!     fn build(freq: u32) -> Self::Decoder {
+//!         PulseDistanceDecoder::new(freq)
+//!     }
+//! }
+//!
+//! impl<const F: u32> ProtocolEncoder<F> for MyProtocol {
+//!     type EncoderData = [u32; 7];
+//!     const DATA: [u32; 7] = encoder_data(MyProtocol::DESCRIPTOR, F);
+//!
+//!     fn encode(cmd: &u16, b: &mut [u32]) -> usize {
+//!         encode::<Self>(&<Self as ProtocolEncoder<F>>::DATA, cmd, b)
+//!     }
+//! }
+//! ```
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+pub use decoder::{PulseDistanceDecoder, PulseDistanceState};
+
+use crate::protocol::Protocol;
+
+/// Timing and frame layout of a pulse distance protocol. Times in us
+#[derive(Copy, Clone, Debug)]
+pub struct PulseDistance {
+    /// Header mark
+    pub header_high: u32,
+    /// Header space
+    pub header_low: u32,
+    /// Data mark
+    pub data_high: u32,
+    /// Space of a zero bit
+    pub zero_low: u32,
+    /// Space of a one bit
+    pub one_low: u32,
+    /// Number of bits in a frame, at most 64
+    pub nbits: u8,
+    /// Bit order
+    pub order: BitOrder,
+    /// The frame ends with a data mark
+    pub stop_bit: bool,
+    /// How a held button is sent
+    pub repeat: Repeat,
+}
+
+/// Order of the bits in a frame
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Least significant bit first
+    Lsb,
+    /// Most significant bit first
+    Msb,
+}
+
+/// Shape of the frames sent when a button is held
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Repeat {
+    /// The full frame is sent again
+    Frame,
+    /// A header with a different space, followed by a stop mark. As in Nec
+    Header {
+        /// Repeat header mark
+        high: u32,
+        /// Repeat header space
+        low: u32,
+        /// Time between the start of two frames. A repeat arriving more than 10 % later than
+        /// this after the last frame is an orphan
+        period: u32,
+    },
+}
+
+impl BitOrder {
+    /// Bit number of the `n`:th received bit in a frame of `nbits` bits
+    pub const fn bit(self, n: u8, nbits: u8) -> u8 {
+        match self {
+            BitOrder::Lsb => n,
+            BitOrder::Msb => nbits - 1 - n,
+        }
+    }
+}
+
+impl PulseDistance {
+    /// Bit number of the `n`:th received bit
+    pub const fn bit(&self, n: u8) -> u8 {
+        self.order.bit(n, self.nbits)
+    }
+}
+
+/// Protocol described by a pulse distance table
+pub trait PulseDistanceProtocol: Protocol {
+    /// Timing and frame layout
+    const DESCRIPTOR: &'static PulseDistance;
+
+    /// Unpack the received bits into a command. The bits of a repeat frame are the bits of
+    /// the last full frame, or of the last frame unpacked into a command. Full frames that
+    /// don't unpack are reported as
+    /// [`DecodingError::Validation`](crate::receiver::DecodingError::Validation)
+    fn unpack(bits: u64, repeat: bool) -> Option<Self::Cmd>;
+
+    /// Pack the command into bits
+    fn pack(cmd: &Self::Cmd) -> u64;
+
+    /// Send the command as a repeat frame
+    fn is_repeat(_cmd: &Self::Cmd) -> bool {
+        false
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    protocol::{
+        pulsedistance::{
+            encoder::{encode, encoder_data},
+            BitOrder, PulseDistance, PulseDistanceDecoder, PulseDistanceProtocol, Repeat,
+        },
+        Protocol,
+    },
+    receiver::{time::InfraMonotonic, BufferInputReceiver, DecoderBuilder},
+    sender::{ProtocolEncoder, PulsedataBuffer},
+};
+
+macro_rules! impl_pulsedistance {
+    ($proto:ty) => {
+        impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for $proto {
+            type Decoder = PulseDistanceDecoder<Mono, $proto>;
+
+            fn build(freq: u32) -> Self::Decoder {
+                PulseDistanceDecoder::new(freq)
+            }
+        }
+
+        impl<const F: u32> ProtocolEncoder<F> for $proto {
+            type EncoderData = [u32; 7];
+            const DATA: [u32; 7] = encoder_data(<$proto>::DESCRIPTOR, F);
+
+            fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+                encode::<Self>(&<Self as ProtocolEncoder<F>>::DATA, cmd, b)
+            }
+        }
+    };
+}
+
+impl_pulsedistance!(Msb16);
+impl_pulsedistance!(Lsb48);
+
+/// 16 bit, MSB first, without stop bit
+struct Msb16;
+
+impl Protocol for Msb16 {
+    type Cmd = u16;
+}
+
+impl PulseDistanceProtocol for Msb16 {
+    const DESCRIPTOR: &'static PulseDistance = &PulseDistance {
+        header_high: 3000,
+        header_low: 1500,
+        data_high: 500,
+        zero_low: 500,
+        one_low: 1500,
+        nbits: 16,
+        order: BitOrder::Msb,
+        stop_bit: false,
+        repeat: Repeat::Frame,
+    };
+
+    fn unpack(bits: u64, _repeat: bool) -> Option<u16> {
+        Some(bits as u16)
+    }
+
+    fn pack(cmd: &u16) -> u64 {
+        u64::from(*cmd)
+    }
+}
+
+/// 48 bit, LSB first, with Nec like repeats
+struct Lsb48;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+struct Lsb48Cmd {
+    bits: u64,
+    repeat: bool,
+}
+
+impl Protocol for Lsb48 {
+    type Cmd = Lsb48Cmd;
+}
+
+impl PulseDistanceProtocol for Lsb48 {
+    const DESCRIPTOR: &'static PulseDistance = &PulseDistance {
+        header_high: 3500,
+        header_low: 1750,
+        data_high: 430,
+        zero_low: 430,
+        one_low: 1300,
+        nbits: 48,
+        order: BitOrder::Lsb,
+        stop_bit: true,
+        repeat: Repeat::Header {
+            high: 3500,
+            low: 3500,
+            period: 100_000,
+        },
+    };
+
+    fn unpack(bits: u64, repeat: bool) -> Option<Lsb48Cmd> {
+        Some(Lsb48Cmd { bits, repeat })
+    }
+
+    fn pack(cmd: &Lsb48Cmd) -> u64 {
+        cmd.bits
+    }
+
+    fn is_repeat(cmd: &Lsb48Cmd) -> bool {
+        cmd.repeat
+    }
+}
+
+#[test]
+fn msb_first() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<64>::new();
+
+    ptb.load::<Msb16, FREQ>(&0x8001);
+
+    // Header, then the one of bit 15 and the zero of bit 14
+    assert_eq!(&ptb.buffer()[..7], &[0, 120, 60, 20, 60, 20, 20]);
+    assert_eq!(ptb.buffer().len(), 35);
+
+    for cmd in [0x0000, 0x8001, 0x1234, 0xFFFF] {
+        ptb.reset();
+        ptb.load::<Msb16, FREQ>(&cmd);
+
+        let mut brecv = BufferInputReceiver::<Msb16>::with_frequenzy(FREQ);
+        assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+    }
+}
+
+#[test]
+fn repeat_header() {
+    const FREQ: u32 = 1_000_000;
+    let mut ptb = PulsedataBuffer::<256>::new();
+
+    let cmd = Lsb48Cmd {
+        bits: 0x8000_1234_5678,
+        repeat: false,
+    };
+    let repeat = Lsb48Cmd {
+        repeat: true,
+        ..cmd
+    };
+
+    ptb.load::<Lsb48, FREQ>(&cmd);
+    ptb.load::<Lsb48, FREQ>(&repeat);
+    ptb.load::<Lsb48, FREQ>(&repeat);
+
+    assert_eq!(ptb.buffer().len(), 100 + 4 + 4);
+
+    let mut brecv = BufferInputReceiver::<Lsb48>::with_frequenzy(FREQ);
+    let cmds = brecv.iter(ptb.buffer()).collect::<std::vec::Vec<_>>();
+
+    assert_eq!(cmds, [cmd, repeat, repeat]);
+}
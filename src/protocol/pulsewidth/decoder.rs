@@ -0,0 +1,182 @@
+use core::marker::PhantomData;
+
+use crate::{
+    protocol::pulsewidth::{Coding, PulseWidthProtocol},
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecodingError, ProtocolDecoder, State,
+    },
+};
+
+const TOL: [u32; 8] = [25, 25, 15, 0, 0, 0, 0, 0];
+/// Index of the header in the spans. Checked separately as it may be equal to a data pulse
+const HEADER: usize = 2;
+/// Index of the pause after a frame that ends early, in the spaces. Any space longer than
+/// the header
+const PAUSE: usize = 3;
+
+/// Decoder for protocols described by a [`PulseWidth`](super::PulseWidth) table
+pub struct PulseWidthDecoder<Mono: InfraMonotonic, Proto> {
+    state: PulseWidthState,
+    bitbuf: u64,
+    proto: PhantomData<Proto>,
+    /// Zero, one and header marks
+    marks: PulseSpans<Mono>,
+    /// Zero, one and header spaces
+    spaces: PulseSpans<Mono>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PulseWidthState {
+    /// Waiting for the header mark
+    Idle,
+    /// Header mark received
+    HeaderLow,
+    /// Number of bits received
+    Data(u8),
+    /// Frame received, with length
+    Done(u8),
+    Err(DecodingError),
+}
+
+impl From<PulseWidthState> for State {
+    fn from(state: PulseWidthState) -> Self {
+        match state {
+            PulseWidthState::Idle => State::Idle,
+            PulseWidthState::HeaderLow | PulseWidthState::Data(_) => State::Receiving,
+            PulseWidthState::Done(_) => State::Done,
+            PulseWidthState::Err(err) => State::Error(err),
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic, Proto: PulseWidthProtocol> PulseWidthDecoder<Mono, Proto> {
+    /// Create a decoder for a sample rate of `freq`
+    pub fn new(freq: u32) -> Self {
+        let pw = Proto::DESCRIPTOR;
+        let marks = [pw.zero_high, pw.one_high, pw.header_high, 0, 0, 0, 0, 0];
+        let pause = pw.header_high + pw.header_low;
+        let spaces = [pw.zero_low, pw.one_low, pw.header_low, pause, 0, 0, 0, 0];
+
+        PulseWidthDecoder {
+            state: PulseWidthState::Idle,
+            bitbuf: 0,
+            proto: PhantomData,
+            marks: PulseSpans::new(freq, &marks, &TOL),
+            spaces: PulseSpans::new(freq, &spaces, &TOL),
+        }
+    }
+
+    /// Add the `n`:th bit
+    fn bit(&mut self, n: u8, one: bool) -> PulseWidthState {
+        let pw = Proto::DESCRIPTOR;
+
+        if one {
+            self.bitbuf |= 1 << pw.bit(n);
+        }
+
+        if n + 1 == pw.nbits {
+            PulseWidthState::Done(n + 1)
+        } else {
+            PulseWidthState::Data(n + 1)
+        }
+    }
+
+    /// A space of `dt` after `n` bits ends the frame
+    fn ends(&self, n: u8, dt: Mono::Duration) -> bool {
+        Proto::ends_early(self.bitbuf, n) && dt >= self.spaces.spans[PAUSE].low()
+    }
+}
+
+impl<Mono, Proto> ProtocolDecoder<Proto, Mono> for PulseWidthDecoder<Mono, Proto>
+where
+    Mono: InfraMonotonic,
+    Proto: PulseWidthProtocol,
+{
+    #[rustfmt::skip]
+    fn event(&mut self, rising: bool, dt: Mono::Duration) -> State {
+        use Pulse::*;
+        use PulseWidthState::*;
+
+        let space_bits = Proto::DESCRIPTOR.coding == Coding::MarkAndSpace;
+
+        self.state = if rising {
+            // End of a space
+            let space = self.spaces.get::<Pulse>(dt).unwrap_or(Invalid);
+            let header = self.spaces.matches(HEADER, dt);
+
+            match (self.state, space) {
+                (HeaderLow,     _) if header    => { self.bitbuf = 0; Data(0) }
+                (HeaderLow,     _)              => Idle,
+                (Data(n),       Zero) if space_bits => self.bit(n, false),
+                (Data(n),       One) if space_bits  => self.bit(n, true),
+                (Data(n),       Zero | One)     => Data(n),
+                (Data(n),       _) if self.ends(n, dt) => Done(n),
+                (Data(_),       _)              => Err(DecodingError::Data),
+                (state,         _)              => state,
+            }
+        } else {
+            // End of a mark
+            let mark = self.marks.get::<Pulse>(dt).unwrap_or(Invalid);
+            let header = self.marks.matches(HEADER, dt);
+
+            match (self.state, mark) {
+                (Idle,          _) if header    => HeaderLow,
+                (Idle,          _)              => Idle,
+                (HeaderLow,     _)              => Idle,
+                (Data(n),       Zero)           => self.bit(n, false),
+                (Data(n),       One)            => self.bit(n, true),
+                (Data(_),       _)              => Err(DecodingError::Data),
+                (state,         _)              => state,
+            }
+        };
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<Proto::Cmd> {
+        match self.state {
+            PulseWidthState::Done(nbits) => Proto::unpack(self.bitbuf, nbits),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = PulseWidthState::Idle;
+        self.bitbuf = 0;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.marks
+    }
+
+    fn timeout(&mut self, dt: Mono::Duration) -> bool {
+        if let PulseWidthState::Data(n) = self.state {
+            if self.ends(n, dt) {
+                self.state = PulseWidthState::Done(n);
+            }
+        }
+
+        matches!(self.state, PulseWidthState::Done(_))
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum Pulse {
+    Zero,
+    One,
+    /// Not a data pulse
+    Invalid,
+}
+
+impl From<usize> for Pulse {
+    fn from(v: usize) -> Self {
+        match v {
+            0 => Pulse::Zero,
+            1 => Pulse::One,
+            _ => Pulse::Invalid,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::protocol::{
+    pulsewidth::{Coding, PulseWidth, PulseWidthProtocol},
+    utils::scale_with_samplerate,
+};
+
+/// Lengths of the pulses in ticks of a `freq` Hz clock, used as `ProtocolEncoder::DATA`
+pub const fn encoder_data(pw: &PulseWidth, freq: u32) -> [u32; 6] {
+    [
+        scale_with_samplerate(pw.header_high, freq),
+        scale_with_samplerate(pw.header_low, freq),
+        scale_with_samplerate(pw.zero_high, freq),
+        scale_with_samplerate(pw.one_high, freq),
+        scale_with_samplerate(pw.zero_low, freq),
+        scale_with_samplerate(pw.one_low, freq),
+    ]
+}
+
+/// Encode `cmd` into `b`, with the pulse lengths from [`encoder_data`]
+pub fn encode<Proto: PulseWidthProtocol>(
+    data: &[u32; 6],
+    cmd: &Proto::Cmd,
+    b: &mut [u32],
+) -> usize {
+    let [header_high, header_low, zero_high, one_high, zero_low, one_low] = *data;
+    let pw = Proto::DESCRIPTOR;
+    let (bits, nbits) = Proto::pack(cmd);
+    let one = |n: u8| (bits >> pw.bit(n)) & 1 != 0;
+
+    b[0] = 0;
+    b[1] = header_high;
+    b[2] = header_low;
+
+    let mut bi = 3;
+    let mut n = 0;
+
+    while n < nbits {
+        let mark_one = one(n);
+        b[bi] = if mark_one { one_high } else { zero_high };
+        n += 1;
+
+        let space_one = match pw.coding {
+            Coding::Mark => mark_one,
+            Coding::MarkAndSpace if n < nbits => {
+                n += 1;
+                one(n - 1)
+            }
+            // The last bit was in a mark, no space needed
+            Coding::MarkAndSpace => {
+                bi += 1;
+                break;
+            }
+        };
+
+        b[bi + 1] = if space_one { one_low } else { zero_low };
+        bi += 2;
+    }
+
+    match pw.coding {
+        // Remove the trailing space
+        Coding::Mark => bi - 1,
+        // Stop mark after the last space
+        Coding::MarkAndSpace if nbits % 2 == 0 => {
+            b[bi] = zero_high;
+            bi + 1
+        }
+        Coding::MarkAndSpace => bi,
+    }
+}
@@ -0,0 +1,141 @@
+//! Generic pulse width protocol
+//!
+//! Decoder and encoder for protocols where the bits are coded in the length of the mark, as in
+//! Sony SIRC, or in the length of both the marks and the spaces. The marks and the spaces are
+//! measured separately, instead of as a mark and space pair as in [`pulsedistance`].
+//!
+//! A protocol is added by describing it in a [`PulseWidth`] table, implementing
+//! [`PulseWidthProtocol`] for it and using the [`PulseWidthDecoder`] and [`encoder::encode`]
+//! for the decoder and encoder.
+//!
+//! ```
+//! use infrared::{
+//!     protocol::{
+//!         pulsedistance::BitOrder,
+//!         pulsewidth::{
+//!             encoder::{encode, encoder_data},
+//!             Coding, PulseWidth, PulseWidthDecoder, PulseWidthProtocol,
+//!         },
+//!         Protocol,
+//!     },
+//!     receiver::{time::InfraMonotonic, DecoderBuilder},
+//!     sender::ProtocolEncoder,
+//! };
+//!
+//! /// Sony SIRC like 12 bit protocol
+//! pub struct MyProtocol;
+//!
+//! impl Protocol for MyProtocol {
+//!     type Cmd = u16;
+//! }
+//!
+//! impl PulseWidthProtocol for MyProtocol {
+//!     const DESCRIPTOR: &'static PulseWidth = &PulseWidth {
+//!         header_high: 2400,
+//!         header_low: 600,
+//!         zero_high: 600,
+//!         one_high: 1200,
+//!         zero_low: 600,
+//!         one_low: 600,
+//!         nbits: 12,
+//!         order: BitOrder::Lsb,
+//!         coding: Coding::Mark,
+//!     };
+//!
+//!     fn unpack(bits: u64, _nbits: u8) -> Option<u16> {
+//!         Some(bits as u16)
+//!     }
+//!
+//!     fn pack(cmd: &u16) -> (u64, u8) {
+//!         (u64::from(*cmd), 12)
+//!     }
+//! }
+//!
+//! impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for MyProtocol {
+//!     type Decoder = PulseWidthDecoder<Mono, MyProtocol>;
+//!
+//!     fn build(freq: u32) -> Self::Decoder {
+//!         PulseWidthDecoder::new(freq)
+//!     }
+//! }
+//!
+//! impl<const F: u32> ProtocolEncoder<F> for MyProtocol {
+//!     type EncoderData = [u32; 6];
+//!     const DATA: [u32; 6] = encoder_data(MyProtocol::DESCRIPTOR, F);
+//!
+//!     fn encode(cmd: &u16, b: &mut [u32]) -> usize {
+//!         encode::<Self>(&<Self as ProtocolEncoder<F>>::DATA, cmd, b)
+//!     }
+//! }
+//! ```
+//!
+//! [`pulsedistance`]: crate::protocol::pulsedistance
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+pub use decoder::{PulseWidthDecoder, PulseWidthState};
+
+use crate::protocol::{pulsedistance::BitOrder, Protocol};
+
+/// Timing and frame layout of a pulse width protocol. Times in us
+#[derive(Copy, Clone, Debug)]
+pub struct PulseWidth {
+    /// Header mark
+    pub header_high: u32,
+    /// Header space
+    pub header_low: u32,
+    /// Mark of a zero bit
+    pub zero_high: u32,
+    /// Mark of a one bit
+    pub one_high: u32,
+    /// Space of a zero bit. With [`Coding::Mark`] the space after a zero mark
+    pub zero_low: u32,
+    /// Space of a one bit. With [`Coding::Mark`] the space after a one mark
+    pub one_low: u32,
+    /// Number of bits in a frame, at most 64. The longest frame of protocols with several
+    /// frame lengths
+    pub nbits: u8,
+    /// Bit order
+    pub order: BitOrder,
+    /// Where the bits are
+    pub coding: Coding,
+}
+
+/// Where the bits of a pulse width protocol are coded
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Coding {
+    /// One bit in every mark
+    Mark,
+    /// One bit in every mark and one in every space. When the last bit is in a space, the frame
+    /// ends with a zero mark
+    MarkAndSpace,
+}
+
+impl PulseWidth {
+    /// Bit number of the `n`:th received bit
+    pub const fn bit(&self, n: u8) -> u8 {
+        self.order.bit(n, self.nbits)
+    }
+}
+
+/// Protocol described by a pulse width table
+pub trait PulseWidthProtocol: Protocol {
+    /// Timing and frame layout
+    const DESCRIPTOR: &'static PulseWidth;
+
+    /// Can a frame end after `n` bits, followed by a pause. For protocols with several frame
+    /// lengths and without a stop bit
+    fn ends_early(_bits: u64, _n: u8) -> bool {
+        false
+    }
+
+    /// Unpack the `nbits` received bits into a command
+    fn unpack(bits: u64, nbits: u8) -> Option<Self::Cmd>;
+
+    /// Pack the command into bits, and the number of bits
+    fn pack(cmd: &Self::Cmd) -> (u64, u8);
+}
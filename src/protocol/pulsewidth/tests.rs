@@ -0,0 +1,218 @@
+use crate::{
+    protocol::{
+        pulsedistance::BitOrder,
+        pulsewidth::{
+            encoder::{encode, encoder_data},
+            Coding, PulseWidth, PulseWidthDecoder, PulseWidthProtocol,
+        },
+        Protocol,
+    },
+    receiver::{time::InfraMonotonic, BufferInputReceiver, DecoderBuilder, Receiver},
+    sender::{ProtocolEncoder, PulsedataBuffer},
+};
+
+macro_rules! impl_pulsewidth {
+    ($proto:ty) => {
+        impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for $proto {
+            type Decoder = PulseWidthDecoder<Mono, $proto>;
+
+            fn build(freq: u32) -> Self::Decoder {
+                PulseWidthDecoder::new(freq)
+            }
+        }
+
+        impl<const F: u32> ProtocolEncoder<F> for $proto {
+            type EncoderData = [u32; 6];
+            const DATA: [u32; 6] = encoder_data(<$proto>::DESCRIPTOR, F);
+
+            fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+                encode::<Self>(&<Self as ProtocolEncoder<F>>::DATA, cmd, b)
+            }
+        }
+    };
+}
+
+/// Sony SIRC 12 bit timing
+struct Mark12;
+
+impl Protocol for Mark12 {
+    type Cmd = u16;
+}
+
+impl PulseWidthProtocol for Mark12 {
+    const DESCRIPTOR: &'static PulseWidth = &PulseWidth {
+        header_high: 2400,
+        header_low: 600,
+        zero_high: 600,
+        one_high: 1200,
+        zero_low: 600,
+        one_low: 600,
+        nbits: 12,
+        order: BitOrder::Lsb,
+        coding: Coding::Mark,
+    };
+
+    fn unpack(bits: u64, _nbits: u8) -> Option<u16> {
+        Some(bits as u16)
+    }
+
+    fn pack(cmd: &u16) -> (u64, u8) {
+        (u64::from(*cmd), 12)
+    }
+}
+
+/// Bits in both marks and spaces, MSB first
+struct MarkSpace<const N: u8>;
+
+impl<const N: u8> Protocol for MarkSpace<N> {
+    type Cmd = u32;
+}
+
+impl<const N: u8> PulseWidthProtocol for MarkSpace<N> {
+    const DESCRIPTOR: &'static PulseWidth = &PulseWidth {
+        header_high: 3000,
+        header_low: 3000,
+        zero_high: 400,
+        one_high: 1000,
+        zero_low: 500,
+        one_low: 1200,
+        nbits: N,
+        order: BitOrder::Msb,
+        coding: Coding::MarkAndSpace,
+    };
+
+    fn unpack(bits: u64, _nbits: u8) -> Option<u32> {
+        Some(bits as u32)
+    }
+
+    fn pack(cmd: &u32) -> (u64, u8) {
+        (u64::from(*cmd), N)
+    }
+}
+
+/// Sony SIRC timing with 12 or 16 bit frames
+struct Variable;
+
+impl Protocol for Variable {
+    type Cmd = (u16, u8);
+}
+
+impl PulseWidthProtocol for Variable {
+    const DESCRIPTOR: &'static PulseWidth = &PulseWidth {
+        nbits: 16,
+        ..*Mark12::DESCRIPTOR
+    };
+
+    fn ends_early(_bits: u64, n: u8) -> bool {
+        n == 12
+    }
+
+    fn unpack(bits: u64, nbits: u8) -> Option<(u16, u8)> {
+        Some((bits as u16, nbits))
+    }
+
+    fn pack(cmd: &(u16, u8)) -> (u64, u8) {
+        (u64::from(cmd.0), cmd.1)
+    }
+}
+
+impl_pulsewidth!(Mark12);
+impl_pulsewidth!(Variable);
+impl_pulsewidth!(MarkSpace<15>);
+impl_pulsewidth!(MarkSpace<16>);
+
+#[test]
+fn mark_coding() {
+    const FREQ: u32 = 1_000_000;
+    let mut ptb = PulsedataBuffer::<64>::new();
+
+    ptb.load::<Mark12, FREQ>(&0x095);
+    assert_eq!(&ptb.buffer()[..7], &[0, 2400, 600, 1200, 600, 600, 600]);
+    assert_eq!(ptb.buffer().len(), 3 + 12 * 2 - 1);
+
+    for cmd in 0..0x1000 {
+        ptb.reset();
+        ptb.load::<Mark12, FREQ>(&cmd);
+
+        let mut brecv = BufferInputReceiver::<Mark12>::with_frequenzy(FREQ);
+        assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+    }
+}
+
+#[test]
+fn mark_and_space_coding() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<64>::new();
+
+    // Odd number of bits ends with a data mark
+    ptb.load::<MarkSpace<15>, FREQ>(&0x4001);
+    assert_eq!(&ptb.buffer()[..5], &[0, 120, 120, 40, 20]);
+    assert_eq!(ptb.buffer().len(), 3 + 15);
+
+    // Even number of bits ends with a stop mark
+    ptb.reset();
+    ptb.load::<MarkSpace<16>, FREQ>(&0x0001);
+    assert_eq!(ptb.buffer().len(), 3 + 16 + 1);
+    assert_eq!(&ptb.buffer()[18..], &[48, 16]);
+
+    for cmd in [0x0000, 0x7FFF, 0x1234, 0x5555, 0x2AAA] {
+        ptb.reset();
+        ptb.load::<MarkSpace<15>, FREQ>(&cmd);
+        let mut brecv = BufferInputReceiver::<MarkSpace<15>>::with_frequenzy(FREQ);
+        assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+
+        ptb.reset();
+        ptb.load::<MarkSpace<16>, FREQ>(&(cmd | 0x8000));
+        let mut brecv = BufferInputReceiver::<MarkSpace<16>>::with_frequenzy(FREQ);
+        assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd | 0x8000));
+    }
+}
+
+#[test]
+fn frame_lengths() {
+    const FREQ: u32 = 1_000_000;
+    let mut ptb = PulsedataBuffer::<64>::new();
+
+    // Followed by a pause
+    for cmd in [(0x0A5, 12), (0xA5A5, 16)] {
+        ptb.reset();
+        ptb.load::<Variable, FREQ>(&cmd);
+
+        let mut buf = std::vec::Vec::from(ptb.buffer());
+        buf.push(20_000);
+
+        let mut brecv = BufferInputReceiver::<Variable>::with_frequenzy(FREQ);
+        assert_eq!(brecv.iter(&buf).next(), Some(cmd));
+    }
+
+    // Without the pause, the short frame is completed by the timeout
+    ptb.reset();
+    ptb.load::<Variable, FREQ>(&(0x0A5, 12));
+
+    let mut recv = Receiver::<Variable>::new(FREQ);
+    for (i, dt) in ptb.buffer().iter().enumerate() {
+        assert_eq!(recv.event(*dt, i & 0x1 == 0), Ok(None));
+    }
+    assert_eq!(recv.timeout(2000), None);
+    assert_eq!(recv.timeout(3000), Some((0x0A5, 12)));
+}
+
+#[test]
+fn clock_frequencies() {
+    one_freq::<20_000>();
+    one_freq::<40_000>();
+    one_freq::<48_000_000>();
+}
+
+fn one_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<64>::new();
+    ptb.load::<MarkSpace<16>, F>(&0xA5C3);
+
+    let mut brecv = BufferInputReceiver::<MarkSpace<16>>::with_frequenzy(F);
+    assert_eq!(
+        brecv.iter(ptb.buffer()).next(),
+        Some(0xA5C3),
+        "Samplerate: {}",
+        F
+    );
+}
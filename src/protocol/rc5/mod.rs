@@ -1,11 +1,17 @@
 //! Philips Rc5
+//!
+//! Supports the extended Rc5X variant, where the second start bit is the inverted bit 6 of
+//! the command, giving commands in the range 0 - 127. Commands below 64 are sent as classic Rc5.
 
 use core::convert::TryInto;
 
 pub mod decoder;
 use crate::{
     cmd::{AddressCommand, Command},
-    protocol::Protocol,
+    protocol::{
+        biphase::{BiPhase, BiPhaseProtocol, Level, Polarity},
+        Protocol,
+    },
     ProtocolId,
 };
 
@@ -16,11 +22,14 @@ mod tests;
 
 const ADDR_MASK: u16 = 0b_0000_0111_1100_0000;
 const CMD_MASK: u16 = 0b_0000_0000_0011_1111;
-const START_MASK: u16 = 0b_0011_0000_0000_0000;
+/// The first start bit, always one
+const START_BIT: u16 = 0b_0010_0000_0000_0000;
+const FIELD_MASK: u16 = 0b_0001_0000_0000_0000;
 const TOGGLE_MASK: u16 = 0b_0000_1000_0000_0000;
 
 const ADDR_SHIFT: u32 = 6;
 const START_SHIFT: u32 = 12;
+const FIELD_SHIFT: u32 = 6;
 const TOGGLE_SHIFT: u32 = 11;
 
 /// Philips Rc5
@@ -30,50 +39,75 @@ impl Protocol for Rc5 {
     type Cmd = Rc5Command;
 }
 
+/// The first start bit is always one and is sent as the header
+const RC5_BIPHASE: BiPhase = BiPhase {
+    unit: 889,
+    header: &[Level::Space(1), Level::Mark(1)],
+    nbits: 13,
+    polarity: Polarity::SpaceMark,
+    double_width: 0,
+};
+
+impl BiPhaseProtocol for Rc5 {
+    const DESCRIPTOR: &'static BiPhase = &RC5_BIPHASE;
+
+    fn unpack(bits: u64, _nbits: u8) -> Option<Rc5Command> {
+        Some(Rc5Command::unpack(bits as u16 | START_BIT))
+    }
+
+    fn pack(cmd: &Rc5Command) -> (u64, u8) {
+        (u64::from(cmd.pack() & !START_BIT), 13)
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Rc5Command {
     pub addr: u8,
+    /// Command, 0 - 127. Bit 6 is sent inverted as the second start bit
     pub cmd: u8,
-    pub start: u8,
     pub toggle: bool,
 }
 
 impl Rc5Command {
     pub const fn new(addr: u8, cmd: u8, toggle: bool) -> Self {
-        Self {
-            addr,
-            cmd,
-            start: 0b11,
-            toggle,
-        }
+        Self { addr, cmd, toggle }
     }
 
     pub const fn unpack(bits: u16) -> Self {
         let addr = ((bits & ADDR_MASK) >> ADDR_SHIFT) as u8;
-        let cmd = (bits & CMD_MASK) as u8;
-        let start = ((bits & START_MASK) >> START_SHIFT) as u8;
+        // The field bit is the inverted command bit 6
+        let field = (((bits & FIELD_MASK) ^ FIELD_MASK) >> (START_SHIFT - FIELD_SHIFT)) as u8;
+        let cmd = (bits & CMD_MASK) as u8 | field;
         let toggle = (bits & TOGGLE_MASK) != 0;
 
-        Self {
-            addr,
-            cmd,
-            start,
-            toggle,
-        }
+        Self { addr, cmd, toggle }
     }
 
     pub fn pack(&self) -> u16 {
-        (u16::from(self.addr) << ADDR_SHIFT)
-            | u16::from(self.cmd)
+        let field = !(u16::from(self.cmd) << (START_SHIFT - FIELD_SHIFT)) & FIELD_MASK;
+
+        (u16::from(self.addr & 0x1F) << ADDR_SHIFT)
+            | (u16::from(self.cmd) & CMD_MASK)
             | (u16::from(self.toggle) << TOGGLE_SHIFT)
-            | (u16::from(self.start) << START_SHIFT)
+            | START_BIT
+            | field
+    }
+
+    /// Is this an Rc5X command, with the second start bit cleared
+    pub const fn is_extended(&self) -> bool {
+        self.cmd & 0x40 != 0
     }
 }
 
 impl Command for Rc5Command {
+    /// There are no repeat frames, the frame is sent again while the key is held
     fn is_repeat(&self) -> bool {
-        self.toggle
+        false
+    }
+
+    fn toggle(&self) -> Option<bool> {
+        Some(self.toggle)
     }
 }
 
@@ -90,7 +124,11 @@ impl AddressCommand for Rc5Command {
 
     fn create(addr: u32, cmd: u32) -> Option<Rc5Command> {
         let addr: u8 = addr.try_into().ok()?;
-        let cmd = cmd.try_into().ok()?;
+        let cmd: u8 = cmd.try_into().ok()?;
+
+        if addr > 0x1F || cmd > 0x7F {
+            return None;
+        }
 
         Some(Rc5Command::new(addr, cmd, false))
     }
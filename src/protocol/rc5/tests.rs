@@ -1,4 +1,5 @@
 use crate::{
+    cmd::AddressCommand,
     protocol::{rc5::Rc5Command, Rc5},
     receiver::BufferInputReceiver,
     remotecontrol::{Action, Button},
@@ -11,6 +12,28 @@ fn rc5_command() {
     assert_eq!(cmd, Rc5Command::unpack(cmd.pack()))
 }
 
+#[test]
+fn rc5x_command() {
+    // Classic Rc5, both start bits set
+    let cmd = Rc5Command::new(20, 15, false);
+    assert_eq!(cmd.pack() >> 12, 0b11);
+    assert!(!cmd.is_extended());
+
+    // Rc5X, the second start bit is the inverted command bit 6
+    let cmd = Rc5Command::new(20, 79, true);
+    assert_eq!(cmd.pack() >> 12, 0b10);
+    assert_eq!(cmd.pack() & 0x3F, 15);
+    assert!(cmd.is_extended());
+    assert_eq!(cmd, Rc5Command::unpack(cmd.pack()));
+
+    assert_eq!(
+        Rc5Command::create(20, 127),
+        Some(Rc5Command::new(20, 127, false))
+    );
+    assert_eq!(Rc5Command::create(20, 128), None);
+    assert_eq!(Rc5Command::create(32, 1), None);
+}
+
 #[test]
 fn test_bufrecv() {
     let dists = [
@@ -70,7 +93,7 @@ fn all_commands() {
     let mut ptb = PulsedataBuffer::<96>::new();
 
     for address in 0..32 {
-        for cmdnum in 0..64 {
+        for cmdnum in 0..128 {
             ptb.reset();
 
             let cmd: Rc5Command = Rc5Command::new(address, cmdnum, false);
@@ -80,8 +103,7 @@ fn all_commands() {
 
             let cmdres = r.iter(&ptb.buf).next().unwrap();
 
-            assert_eq!(cmd.addr, cmdres.addr);
-            assert_eq!(cmd.cmd, cmdres.cmd);
+            assert_eq!(cmd, cmdres);
         }
     }
 }
@@ -5,12 +5,18 @@ use crate::{
     ProtocolId,
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Rc6Command {
     pub addr: u8,
     pub cmd: u8,
     pub toggle: bool,
+    /// Mode. 0 for the standard 16 bit Rc6, 6 for Rc6 6A
+    pub mode: u8,
+    /// Customer code, only used in mode 6. 8 or 16 bits, or 4 bits in the 20 bit variant
+    pub customer: u16,
+    /// Number of payload bits. 16 in mode 0 and 20, 24 or 32 in mode 6
+    pub len: u8,
 }
 
 impl Rc6Command {
@@ -19,27 +25,109 @@ impl Rc6Command {
             addr,
             cmd,
             toggle: false,
+            mode: 0,
+            customer: 0,
+            len: 16,
         }
     }
 
+    /// Create a mode 6A command. Customer codes with the top bit set are sent as 16 bits,
+    /// with a 32 bit payload, and customer codes up to 0x7F as 8 bits with a 24 bit payload.
+    /// Returns `None` for the customer codes in between, they don't fit either variant.
+    ///
+    /// In the 32 bit variant the address is 7 bits, the top bit is the toggle bit.
+    pub fn new_6a(customer: u16, addr: u8, cmd: u8) -> Option<Self> {
+        let len = match customer {
+            0..=0x7F => 24,
+            0x8000..=0xFFFF => 32,
+            _ => return None,
+        };
+
+        Some(Self {
+            addr,
+            cmd,
+            toggle: false,
+            mode: 6,
+            customer,
+            len,
+        })
+    }
+
     pub fn from_bits(bits: u16, toggle: bool) -> Self {
         let addr = (bits >> 8) as u8;
         let cmd = (bits & 0xFF) as u8;
-        Self { addr, cmd, toggle }
+        Self {
+            toggle,
+            ..Self::new(addr, cmd)
+        }
+    }
+
+    /// Create command from a payload of `len` bits. `toggle` is the value of the trailer bit
+    pub fn from_payload(mode: u8, payload: u32, len: u8, toggle: bool) -> Self {
+        let addr = (payload >> 8) as u8;
+        let cmd = payload as u8;
+        let customer = payload.checked_shr(16).unwrap_or(0) as u16;
+
+        if mode == 6 && len == 32 {
+            // Toggle bit is the top bit of the address
+            Self {
+                addr: addr & 0x7F,
+                cmd,
+                toggle: addr & 0x80 != 0,
+                mode,
+                customer,
+                len,
+            }
+        } else {
+            Self {
+                addr,
+                cmd,
+                toggle,
+                mode,
+                customer,
+                len,
+            }
+        }
+    }
+
+    /// The payload bits
+    pub fn payload(&self) -> u32 {
+        let addr = if self.mode == 6 && self.len == 32 {
+            (self.addr & 0x7F) | u8::from(self.toggle) << 7
+        } else {
+            self.addr
+        };
+
+        u32::from(self.customer) << 16 | u32::from(addr) << 8 | u32::from(self.cmd)
+    }
+
+    /// The value of the toggle bit in the trailer
+    pub fn trailer(&self) -> bool {
+        self.toggle && !(self.mode == 6 && self.len == 32)
     }
 }
 
 impl Command for Rc6Command {
+    /// There are no repeat frames, the frame is sent again while the key is held
     fn is_repeat(&self) -> bool {
-        self.toggle
+        false
+    }
+
+    fn toggle(&self) -> Option<bool> {
+        Some(self.toggle)
     }
 }
 
 impl AddressCommand for Rc6Command {
     const ID: ProtocolId = ProtocolId::Rc6;
 
+    fn raw(&self) -> u64 {
+        self.payload().into()
+    }
+
+    /// Customer code in bit 8 - 23 and address in bit 0 - 7
     fn address(&self) -> u32 {
-        self.addr.into()
+        u32::from(self.customer) << 8 | u32::from(self.addr)
     }
 
     fn command(&self) -> u32 {
@@ -47,6 +135,11 @@ impl AddressCommand for Rc6Command {
     }
 
     fn create(addr: u32, cmd: u32) -> Option<Self> {
-        Some(Rc6Command::new(addr.try_into().ok()?, cmd.try_into().ok()?))
+        let cmd = cmd.try_into().ok()?;
+
+        match addr >> 8 {
+            0 => Some(Rc6Command::new(addr as u8, cmd)),
+            customer => Rc6Command::new_6a(customer.try_into().ok()?, addr as u8, cmd),
+        }
     }
 }
@@ -1,6 +1,16 @@
 //! Philips Rc6
+//!
+//! Supports mode 0, with 8 bit address and 8 bit command, and mode 6A with a 8 or 16 bit
+//! customer code in front of the address. Mode 6A is used by Windows Media Center remotes,
+//! Xbox remotes and many set-top boxes.
+//!
+//! The length of a mode 6A payload is found from the first bit of the customer code, 32 bits
+//! when set and 24 bits otherwise. 20 bit frames are found by the pause after the frame.
 
-use crate::protocol::Protocol;
+use crate::protocol::{
+    biphase::{BiPhase, BiPhaseProtocol, Level, Polarity},
+    Protocol,
+};
 
 mod cmd;
 mod decoder;
@@ -16,3 +26,70 @@ pub struct Rc6 {}
 impl Protocol for Rc6 {
     type Cmd = Rc6Command;
 }
+
+/// Mode and trailer bits in front of the payload
+const MODE_BITS: u8 = 4;
+
+/// The leader and the start bit, always one, are sent as the header. The bits are the three
+/// mode bits, the double width trailer bit and the payload
+const RC6_BIPHASE: BiPhase = BiPhase {
+    unit: 444,
+    header: &[
+        Level::Mark(6),
+        Level::Space(2),
+        Level::Mark(1),
+        Level::Space(1),
+    ],
+    nbits: MODE_BITS + 32,
+    polarity: Polarity::MarkSpace,
+    double_width: 1 << 3,
+};
+
+impl BiPhaseProtocol for Rc6 {
+    const DESCRIPTOR: &'static BiPhase = &RC6_BIPHASE;
+
+    fn nbits(bits: u64, n: u8) -> u8 {
+        if n <= MODE_BITS {
+            return RC6_BIPHASE.nbits;
+        }
+
+        let mode = (bits >> (n - 3)) & 0b111;
+        // First bit of the payload
+        let first = (bits >> (n - MODE_BITS - 1)) & 1;
+
+        match (mode, first) {
+            // Long customer code
+            (6, 1) => MODE_BITS + 32,
+            (6, _) => MODE_BITS + 24,
+            _ => MODE_BITS + 16,
+        }
+    }
+
+    fn ends_early(bits: u64, n: u8) -> bool {
+        // Mode 6 frames with 20 bit payload end with the pause
+        n == MODE_BITS + 20 && (bits >> (n - 3)) & 0b111 == 6
+    }
+
+    fn unpack(bits: u64, nbits: u8) -> Option<Rc6Command> {
+        let len = nbits - MODE_BITS;
+        let mode = (bits >> (len + 1)) & 0b111;
+        let trailer = (bits >> len) & 1 != 0;
+        let payload = bits & ((1 << len) - 1);
+
+        Some(Rc6Command::from_payload(
+            mode as u8,
+            payload as u32,
+            len,
+            trailer,
+        ))
+    }
+
+    fn pack(cmd: &Rc6Command) -> (u64, u8) {
+        let len = cmd.len.min(32);
+        let payload = u64::from(cmd.payload()) & ((1 << len) - 1);
+        let bits =
+            u64::from(cmd.mode & 0b111) << (len + 1) | u64::from(cmd.trailer()) << len | payload;
+
+        (bits, MODE_BITS + len)
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
+    cmd::AddressCommand,
     protocol::{rc6::Rc6Command, Rc6},
     receiver::{BufferInputReceiver, ProtocolDecoder},
+    remotecontrol::{rc6::MceRemote, Action, Button, RemoteControlModel},
     sender::PulsedataBuffer,
     Receiver,
 };
@@ -89,3 +91,102 @@ fn all_commands() {
         }
     }
 }
+
+#[test]
+fn mode6a_payload() {
+    // Mce remote, Ok button with toggle bit set
+    let cmd = Rc6Command::from_payload(6, 0x800F_8422, 32, false);
+
+    assert_eq!(cmd.customer, 0x800F);
+    assert_eq!(cmd.addr, 0x04);
+    assert_eq!(cmd.cmd, 0x22);
+    assert!(cmd.toggle);
+    assert_eq!(cmd.payload(), 0x800F_8422);
+    assert_eq!(cmd.address(), 0x800F04);
+
+    let cmd = Rc6Command::from_payload(6, 0x26_1234, 24, true);
+    assert_eq!((cmd.customer, cmd.addr, cmd.cmd), (0x26, 0x12, 0x34));
+    assert!(cmd.trailer());
+
+    assert_eq!(
+        Rc6Command::create(0x800F04, 0x22),
+        Rc6Command::new_6a(0x800F, 0x04, 0x22)
+    );
+    assert_eq!(
+        Rc6Command::create(0x2612, 0x34),
+        Rc6Command::new_6a(0x26, 0x12, 0x34)
+    );
+    assert_eq!(Rc6Command::create(0x10_0012, 0x34), None);
+    assert_eq!(Rc6Command::create(0x8012, 0x34), None);
+    assert_eq!(Rc6Command::new_6a(0x80, 0x12, 0x34), None);
+    assert_eq!(Rc6Command::new_6a(0x7FFF, 0x12, 0x34), None);
+}
+
+#[test]
+fn mode6a_commands() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+
+    for customer in [0x800F, 0xFFFF, 0x00, 0x26, 0x7F] {
+        for toggle in [false, true] {
+            for cmdnum in 0..255 {
+                ptb.reset();
+
+                let cmd = Rc6Command {
+                    toggle,
+                    ..Rc6Command::new_6a(customer, cmdnum & 0x7F, cmdnum).unwrap()
+                };
+                ptb.load::<Rc6, FREQ>(&cmd);
+
+                let mut recv = BufferInputReceiver::<Rc6>::with_frequenzy(FREQ);
+                let cmdres = recv.iter(ptb.buffer()).next();
+
+                assert_eq!(cmdres, Some(cmd));
+            }
+        }
+    }
+}
+
+#[test]
+fn mode6a_20bit() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+
+    let cmd = Rc6Command {
+        len: 20,
+        ..Rc6Command::new_6a(0x5, 0x12, 0x34).unwrap()
+    };
+    ptb.load::<Rc6, FREQ>(&cmd);
+
+    // Two frames, the 20 bit frame is complete when the next frame starts
+    let mut buf = std::vec::Vec::from(ptb.buffer());
+    buf.push(1000);
+    buf.extend_from_slice(&ptb.buffer()[1..]);
+    buf.push(1000);
+
+    let mut recv = BufferInputReceiver::<Rc6>::with_frequenzy(FREQ);
+    let cmds = recv.iter(&buf).collect::<std::vec::Vec<_>>();
+
+    assert_eq!(cmds, [cmd, cmd]);
+    assert_eq!(cmds[0].customer, 0x5);
+}
+
+#[test]
+fn mce_remote() {
+    const FREQ: u32 = 1_000_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+
+    let cmd = MceRemote::encode(&Action::Enter).unwrap();
+    assert_eq!(cmd.payload(), 0x800F_0422);
+
+    for toggle in [false, true] {
+        ptb.reset();
+        ptb.load::<Rc6, FREQ>(&Rc6Command { toggle, ..cmd });
+
+        let mut recv = BufferInputReceiver::<Rc6, u32, Button<MceRemote>>::with_frequenzy(FREQ);
+        let button = recv.iter(ptb.buffer()).next().unwrap();
+
+        assert_eq!(button.action(), Some(Action::Enter));
+        assert_eq!(button.command().toggle, toggle);
+    }
+}
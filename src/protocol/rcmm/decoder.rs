@@ -0,0 +1,130 @@
+use crate::{
+    protocol::{
+        rcmm::{RcMmCommand, DATA_HIGH, HEADER_HIGH, HEADER_LOW, SYMBOL_LOW},
+        RcMm,
+    },
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecoderBuilder, DecodingError, ProtocolDecoder, State,
+    },
+};
+
+const HEADER_MARK: usize = 4;
+const HEADER_SPACE: usize = 5;
+const PAUSE: usize = 6;
+
+const PULSE: [u32; 8] = [
+    // Data mark and space of the symbols, the total is less sensitive to receiver distortion
+    DATA_HIGH + SYMBOL_LOW[0],
+    DATA_HIGH + SYMBOL_LOW[1],
+    DATA_HIGH + SYMBOL_LOW[2],
+    DATA_HIGH + SYMBOL_LOW[3],
+    HEADER_HIGH,
+    HEADER_LOW,
+    // Longer than the longest symbol, the space after the stop mark
+    2 * SYMBOL_LOW[3],
+    0,
+];
+const TOL: [u32; 8] = [17, 12, 10, 8, 25, 25, 0, 0];
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for RcMm {
+    type Decoder = RcMmDecoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        RcMmDecoder {
+            state: RcMmState::Idle,
+            bitbuf: 0,
+            dt_save: Mono::ZERO_DURATION,
+            spans: PulseSpans::new(freq, &PULSE, &TOL),
+        }
+    }
+}
+
+pub struct RcMmDecoder<Mono: InfraMonotonic> {
+    state: RcMmState,
+    bitbuf: u32,
+    dt_save: Mono::Duration,
+    spans: PulseSpans<Mono>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RcMmState {
+    Idle,
+    /// Number of bits received
+    Data(u8),
+    /// Frame received, with length
+    Done(u8),
+    Err(DecodingError),
+}
+
+impl From<RcMmState> for State {
+    fn from(state: RcMmState) -> Self {
+        match state {
+            RcMmState::Idle => State::Idle,
+            RcMmState::Data(_) => State::Receiving,
+            RcMmState::Done(_) => State::Done,
+            RcMmState::Err(err) => State::Error(err),
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic> ProtocolDecoder<RcMm, Mono> for RcMmDecoder<Mono> {
+    #[rustfmt::skip]
+    fn event(&mut self, rising: bool, dt: Mono::Duration) -> State {
+        use RcMmState::*;
+
+        if rising {
+            let header = self.spans.matches(HEADER_MARK, self.dt_save)
+                && self.spans.matches(HEADER_SPACE, dt);
+            let symbol = self.spans.get::<usize>(self.dt_save + dt).filter(|s| *s < 4);
+
+            self.state = match (self.state, symbol) {
+                (Idle,      _) if header    => { self.bitbuf = 0; Data(0) }
+                (Idle,      _)              => Idle,
+
+                (Data(n),   Some(s)) if n < 32 => { self.bitbuf = self.bitbuf << 2 | s as u32; Data(n + 2) }
+                // Stop mark and the pause after the frame
+                (Data(n @ (12 | 24 | 32)), None) => Done(n),
+                (Data(_),   _)              => Err(DecodingError::Data),
+
+                (Done(n),   _)              => Done(n),
+                (Err(err),  _)              => Err(err),
+            };
+
+            self.dt_save = Mono::ZERO_DURATION;
+        } else {
+            self.dt_save = dt;
+        }
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<RcMmCommand> {
+        match self.state {
+            RcMmState::Done(nbits) => RcMmCommand::unpack(self.bitbuf, nbits),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = RcMmState::Idle;
+        self.bitbuf = 0;
+        self.dt_save = Mono::ZERO_DURATION;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.spans
+    }
+
+    fn timeout(&mut self, dt: Mono::Duration) -> bool {
+        // The stop mark has been received and no symbol follows
+        if let RcMmState::Data(n @ (12 | 24 | 32)) = self.state {
+            if dt >= self.spans.spans[PAUSE].low() {
+                self.state = RcMmState::Done(n);
+            }
+        }
+
+        matches!(self.state, RcMmState::Done(_))
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    protocol::{
+        rcmm::{DATA_HIGH, FRAME_PERIOD, HEADER_HIGH, HEADER_LOW, SYMBOL_LOW},
+        utils::scale_with_samplerate,
+        RcMm,
+    },
+    sender::ProtocolEncoder,
+};
+
+impl<const F: u32> ProtocolEncoder<F> for RcMm {
+    type EncoderData = [u32; 8];
+    const DATA: Self::EncoderData = [
+        scale_with_samplerate(HEADER_HIGH, F),
+        scale_with_samplerate(HEADER_LOW, F),
+        scale_with_samplerate(DATA_HIGH, F),
+        scale_with_samplerate(SYMBOL_LOW[0], F),
+        scale_with_samplerate(SYMBOL_LOW[1], F),
+        scale_with_samplerate(SYMBOL_LOW[2], F),
+        scale_with_samplerate(SYMBOL_LOW[3], F),
+        scale_with_samplerate(FRAME_PERIOD, F),
+    ];
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        let [header_high, header_low, data_high, s0, s1, s2, s3, period] =
+            <Self as ProtocolEncoder<F>>::DATA;
+        let symbols = [s0, s1, s2, s3];
+
+        let (bits, nbits) = cmd.pack();
+
+        b[0] = 0;
+        b[1] = header_high;
+        b[2] = header_low;
+
+        let mut frame_len = header_high + header_low;
+        let mut bi = 3;
+
+        for n in (0..nbits).step_by(2).rev() {
+            let space = symbols[((bits >> n) & 0x3) as usize];
+            b[bi] = data_high;
+            b[bi + 1] = space;
+            frame_len += data_high + space;
+            bi += 2;
+        }
+
+        // Stop mark, and a pause to the end of the frame period to let the receiver find the end
+        b[bi] = data_high;
+        b[bi + 1] = period.saturating_sub(frame_len + data_high);
+
+        bi + 2
+    }
+}
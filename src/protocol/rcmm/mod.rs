@@ -0,0 +1,155 @@
+//! Philips RC-MM
+//!
+//! Pulse distance coded protocol with two bits per symbol. A frame is a header, 6, 12 or 16
+//! symbols, MSB first, and a stop mark. Every symbol is a short mark followed by one of four
+//! space lengths, one for each of the values 0 - 3.
+//!
+//! The frame length selects the mode:
+//!  - 12 bits, basic mode. 2 bit mode, 2 bit address and 8 bit command. The mode is never zero
+//!  - 24 bits, extended mode. Four zero bits, 2 bit mode, 6 bit address and 12 bit command
+//!  - 32 bits, OEM mode. The bits `000001`, 6 bit customer id and 20 bit command
+
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::Protocol,
+    ProtocolId,
+};
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+const HEADER_HIGH: u32 = 417;
+const HEADER_LOW: u32 = 278;
+const DATA_HIGH: u32 = 167;
+/// Space after the data mark for the symbols 0 - 3
+const SYMBOL_LOW: [u32; 4] = [278, 444, 611, 778];
+/// Time from the start of one frame to the start of the next
+const FRAME_PERIOD: u32 = 28_000;
+
+/// RC-MM
+pub struct RcMm;
+
+impl Protocol for RcMm {
+    type Cmd = RcMmCommand;
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RcMmCommand {
+    /// Mode. 1 keyboard, 2 mouse and 3 remote control. 0 in OEM mode
+    pub mode: u8,
+    /// Address, 2 bits in basic and 6 bits in extended mode. Customer id in OEM mode
+    pub addr: u8,
+    /// Command, 8 bits in basic, 12 bits in extended and 20 bits in OEM mode
+    pub cmd: u32,
+    /// Frame length in bits. 12, 24 or 32
+    pub nbits: u8,
+}
+
+impl RcMmCommand {
+    /// Basic mode command. Returns `None` for mode 0, it isn't a basic mode frame
+    pub const fn new(mode: u8, addr: u8, cmd: u8) -> Option<Self> {
+        if mode == 0 || mode > 3 {
+            return None;
+        }
+
+        Some(Self {
+            mode,
+            addr,
+            cmd: cmd as u32,
+            nbits: 12,
+        })
+    }
+
+    /// Extended mode command
+    pub const fn new_extended(mode: u8, addr: u8, cmd: u16) -> Self {
+        Self {
+            mode,
+            addr,
+            cmd: cmd as u32,
+            nbits: 24,
+        }
+    }
+
+    /// OEM mode command
+    pub const fn new_oem(customer: u8, cmd: u32) -> Self {
+        Self {
+            mode: 0,
+            addr: customer,
+            cmd,
+            nbits: 32,
+        }
+    }
+
+    /// Unpack a frame of `nbits` bits
+    pub fn unpack(bits: u32, nbits: u8) -> Option<Self> {
+        let byte = |shift: u32, mask: u32| ((bits >> shift) & mask) as u8;
+
+        match nbits {
+            12 => Self::new(byte(10, 0x3), byte(8, 0x3), bits as u8),
+            24 if bits >> 20 == 0 => Some(Self {
+                cmd: bits & 0xFFF,
+                ..Self::new_extended(byte(18, 0x3), byte(12, 0x3F), 0)
+            }),
+            32 if bits >> 26 == 0b00_0001 => Some(Self::new_oem(byte(20, 0x3F), bits & 0xF_FFFF)),
+            _ => None,
+        }
+    }
+
+    /// Pack the command into the bits of the frame and the frame length
+    pub fn pack(&self) -> (u32, u8) {
+        let mode = u32::from(self.mode & 0x3);
+        let addr = u32::from(self.addr);
+
+        match self.nbits {
+            24 => (mode << 18 | (addr & 0x3F) << 12 | self.cmd & 0xFFF, 24),
+            32 => (
+                0b00_0001 << 26 | (addr & 0x3F) << 20 | self.cmd & 0xF_FFFF,
+                32,
+            ),
+            _ => (mode << 10 | (addr & 0x3) << 8 | self.cmd & 0xFF, 12),
+        }
+    }
+}
+
+impl Command for RcMmCommand {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+}
+
+impl AddressCommand for RcMmCommand {
+    const ID: ProtocolId = ProtocolId::RcMm;
+
+    fn raw(&self) -> u64 {
+        self.pack().0.into()
+    }
+
+    /// Mode in bit 8 - 9 and address in bit 0 - 7
+    fn address(&self) -> u32 {
+        u32::from(self.mode) << 8 | u32::from(self.addr)
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd
+    }
+
+    /// Mode 0 creates an OEM mode command. Other modes a basic mode command if the address and
+    /// command fits, otherwise an extended mode command
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        let mode: u8 = (addr >> 8).try_into().ok()?;
+        let addr = (addr & 0xFF) as u8;
+
+        match (mode, addr, cmd) {
+            (0, 0..=0x3F, 0..=0xF_FFFF) => Some(Self::new_oem(addr, cmd)),
+            (1..=3, 0..=0x3, 0..=0xFF) => Self::new(mode, addr, cmd as u8),
+            (1..=3, 0..=0x3F, 0..=0xFFF) => Some(Self::new_extended(mode, addr, cmd as u16)),
+            _ => None,
+        }
+    }
+}
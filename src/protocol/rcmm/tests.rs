@@ -0,0 +1,146 @@
+use crate::{
+    cmd::AddressCommand,
+    protocol::{rcmm::RcMmCommand, RcMm},
+    receiver::{BufferInputReceiver, Receiver},
+    sender::PulsedataBuffer,
+};
+
+#[test]
+fn cmd_pack_unpack() {
+    let cmd = RcMmCommand::new(3, 2, 0xA5).unwrap();
+    assert_eq!(cmd.pack(), (0xEA5, 12));
+    assert_eq!(RcMmCommand::unpack(0xEA5, 12), Some(cmd));
+
+    let cmd = RcMmCommand::new_extended(1, 0x2A, 0x123);
+    assert_eq!(cmd.pack(), (0x06A123, 24));
+    assert_eq!(RcMmCommand::unpack(0x06A123, 24), Some(cmd));
+
+    let cmd = RcMmCommand::new_oem(0x15, 0xABCDE);
+    assert_eq!(cmd.pack(), (0x055A_BCDE, 32));
+    assert_eq!(RcMmCommand::unpack(0x055A_BCDE, 32), Some(cmd));
+
+    // Escape bits in a basic frame, and missing escape bits in longer frames
+    assert_eq!(RcMmCommand::unpack(0x0A5, 12), None);
+    assert_eq!(RcMmCommand::unpack(0x16A123, 24), None);
+    assert_eq!(RcMmCommand::unpack(0x095A_BCDE, 32), None);
+    assert_eq!(RcMmCommand::unpack(0, 16), None);
+
+    // Mode 0 is not a basic mode frame
+    assert_eq!(RcMmCommand::new(0, 2, 0xA5), None);
+    assert_eq!(RcMmCommand::new(4, 2, 0xA5), None);
+}
+
+#[test]
+fn cmd_create() {
+    assert_eq!(
+        RcMmCommand::create(0x302, 0xA5),
+        RcMmCommand::new(3, 2, 0xA5)
+    );
+    assert_eq!(
+        RcMmCommand::create(0x12A, 0x123),
+        Some(RcMmCommand::new_extended(1, 0x2A, 0x123))
+    );
+    assert_eq!(
+        RcMmCommand::create(0x015, 0xABCDE),
+        Some(RcMmCommand::new_oem(0x15, 0xABCDE))
+    );
+
+    let cmd = RcMmCommand::new_extended(2, 0x3F, 0xFFF);
+    assert_eq!(RcMmCommand::create(cmd.address(), cmd.command()), Some(cmd));
+
+    assert_eq!(RcMmCommand::create(0x400, 0), None);
+    assert_eq!(RcMmCommand::create(0x140, 0), None);
+    assert_eq!(RcMmCommand::create(0x100, 0x1000), None);
+    assert_eq!(RcMmCommand::create(0x000, 0x10_0000), None);
+}
+
+#[test]
+fn encode_frame() {
+    let mut ptb = PulsedataBuffer::<48>::new();
+    ptb.load::<RcMm, 1_000_000>(&RcMmCommand::new(3, 2, 0xA5).unwrap());
+
+    // Header, symbols 3, 2, 2, 2, 1, 1 and the stop mark
+    assert_eq!(
+        &ptb.buffer()[..16],
+        &[0, 417, 278, 167, 778, 167, 611, 167, 611, 167, 611, 167, 444, 167, 444, 167]
+    );
+    // Padded to the frame period
+    assert_eq!(ptb.buffer().iter().sum::<u32>(), 28_000);
+}
+
+#[test]
+fn all_modes() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<48>::new();
+
+    let mut cmds = std::vec::Vec::new();
+    for cmdnum in 0..=255u16 {
+        cmds.push(
+            RcMmCommand::new(1 + cmdnum as u8 % 3, cmdnum as u8 & 0x3, cmdnum as u8).unwrap(),
+        );
+        cmds.push(RcMmCommand::new_extended(
+            2,
+            cmdnum as u8 & 0x3F,
+            cmdnum << 4 | 0xF,
+        ));
+        cmds.push(RcMmCommand::new_oem(
+            0x3F - (cmdnum as u8 & 0x3F),
+            u32::from(cmdnum) << 12 | 0x5A5,
+        ));
+    }
+
+    for cmd in cmds {
+        ptb.reset();
+        ptb.load::<RcMm, FREQ>(&cmd);
+
+        let mut brecv = BufferInputReceiver::<RcMm>::with_frequenzy(FREQ);
+        assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+    }
+}
+
+#[test]
+fn truncated_frame() {
+    let mut ptb = PulsedataBuffer::<48>::new();
+    ptb.load::<RcMm, 1_000_000>(&RcMmCommand::new_extended(1, 0x2A, 0x123));
+
+    // Drop two symbols, 20 bits is not a valid frame length
+    let mut buf = std::vec::Vec::from(ptb.buffer());
+    buf.drain(3..7);
+
+    let mut brecv = BufferInputReceiver::<RcMm>::with_frequenzy(1_000_000);
+    assert_eq!(brecv.iter(&buf).next(), None);
+}
+
+#[test]
+fn timeout() {
+    let mut ptb = PulsedataBuffer::<48>::new();
+    let cmd = RcMmCommand::new_extended(1, 0x2A, 0x123);
+    ptb.load::<RcMm, 1_000_000>(&cmd);
+
+    // Up to the end of the stop mark
+    let mut recv = Receiver::<RcMm>::new(1_000_000);
+    for (i, dt) in ptb.buffer()[..2 + 2 * 12 + 2].iter().enumerate() {
+        assert_eq!(recv.event(*dt, i & 0x1 == 0), Ok(None));
+    }
+
+    // Could be the space of another symbol
+    assert_eq!(recv.timeout(900), None);
+    assert_eq!(recv.timeout(2000), Some(cmd));
+}
+
+#[test]
+fn clock_frequencies() {
+    one_freq::<40_000>();
+    one_freq::<1_000_000>();
+    one_freq::<48_000_000>();
+}
+
+fn one_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<48>::new();
+    let cmd = RcMmCommand::new_oem(0x15, 0xABCDE);
+    ptb.load::<RcMm, F>(&cmd);
+
+    let mut brecv = BufferInputReceiver::<RcMm>::with_frequenzy(F);
+
+    assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+}
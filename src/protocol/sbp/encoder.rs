@@ -0,0 +1,54 @@
+use crate::{
+    protocol::{sbp::TIMING, utils::scale_with_samplerate, Sbp},
+    sender::ProtocolEncoder,
+};
+
+impl<const F: u32> ProtocolEncoder<F> for Sbp {
+    type EncoderData = [u32; 6];
+    const DATA: Self::EncoderData = [
+        scale_with_samplerate(TIMING.hh, F),
+        scale_with_samplerate(TIMING.hl, F),
+        scale_with_samplerate(TIMING.pause, F),
+        scale_with_samplerate(TIMING.data, F),
+        scale_with_samplerate(TIMING.zero, F),
+        scale_with_samplerate(TIMING.one, F),
+    ];
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        let [hh, hl, pause, data, zero, one] = <Self as ProtocolEncoder<F>>::DATA;
+
+        b[0] = 0;
+        b[1] = hh;
+        b[2] = hl;
+
+        let mut bi = 3;
+
+        for i in 0..16 {
+            b[bi] = data;
+            b[bi + 1] = if (cmd.address >> i) & 1 != 0 {
+                one
+            } else {
+                zero
+            };
+            bi += 2;
+        }
+
+        // Divider
+        b[bi] = data;
+        b[bi + 1] = pause;
+        bi += 2;
+
+        let bits = cmd.pack();
+
+        for i in 0..20 {
+            b[bi] = data;
+            b[bi + 1] = if (bits >> i) & 1 != 0 { one } else { zero };
+            bi += 2;
+        }
+
+        // Stop bit
+        b[bi] = data;
+
+        bi + 1
+    }
+}
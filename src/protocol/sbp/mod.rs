@@ -6,21 +6,27 @@
 //! Then a pause and then 4 bits of unknown function (could be repeat indicator?)
 //! After this the 8 bit command is sent twice, second time inverted.
 //!
+//! This is the 36 bit Samsung protocol, Samsung36, also used by Samsung soundbars and air
+//! conditioners. [`Samsung36`] and [`Samsung36Command`] are aliases for it.
+//!
 
 use core::convert::TryInto;
 
-#[cfg(feature = "remotes")]
-use crate::receiver::{DecodingError, ProtocolDecoder, State};
 use crate::{
     cmd::{AddressCommand, Command},
     protocol::Protocol,
     receiver::{
         time::{InfraMonotonic, PulseSpans},
-        DecoderBuilder,
+        DecoderBuilder, DecodingError, ProtocolDecoder, State,
     },
     ProtocolId,
 };
 
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
 /// Samsung BluRay player protocol
 pub struct Sbp;
 
@@ -28,11 +34,41 @@ impl Protocol for Sbp {
     type Cmd = SbpCommand;
 }
 
+/// Samsung36, the same protocol as [`Sbp`]
+pub type Samsung36 = Sbp;
+
+/// Samsung36 command
+pub type Samsung36Command = SbpCommand;
+
+struct SbpTiming {
+    /// Header high
+    hh: u32,
+    /// Header low
+    hl: u32,
+    /// Pause low
+    pause: u32,
+    /// Data high
+    data: u32,
+    /// Zero low
+    zero: u32,
+    /// One low
+    one: u32,
+}
+
+const TIMING: SbpTiming = SbpTiming {
+    hh: 4500,
+    hl: 4500,
+    pause: 4500,
+    data: 500,
+    zero: 500,
+    one: 1500,
+};
+
 const PULSE: [u32; 8] = [
-    (4500 + 4500),
-    (500 + 4500),
-    (500 + 500),
-    (500 + 1500),
+    (TIMING.hh + TIMING.hl),
+    (TIMING.data + TIMING.pause),
+    (TIMING.data + TIMING.zero),
+    (TIMING.data + TIMING.one),
     0,
     0,
     0,
@@ -63,18 +99,20 @@ pub struct SbpDecoder<Mono: InfraMonotonic> {
     spans: PulseSpans<Mono>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct SbpCommand {
     pub address: u16,
     pub command: u8,
+    /// The 4 bits sent before the command, function unknown
+    pub unknown: u8,
     pub valid: bool,
 }
 
 impl SbpCommand {
-    pub fn unpack(address: u16, mut command: u32) -> Self {
-        // Discard the 4 unknown bits
-        command >>= 4;
+    pub fn unpack(address: u16, command: u32) -> Self {
+        let unknown = (command & 0xF) as u8;
+        let command = command >> 4;
 
         // Check the checksum
         let valid = ((command ^ (command >> 8)) & 0xFF) == 0xFF;
@@ -82,9 +120,17 @@ impl SbpCommand {
         Self {
             address,
             command: (command) as u8,
+            unknown,
             valid,
         }
     }
+
+    /// Pack the 20 bits sent after the divider: the unknown bits, the command and the inverted command
+    pub fn pack(&self) -> u32 {
+        u32::from(self.unknown & 0xF)
+            | u32::from(self.command) << 4
+            | u32::from(!self.command) << 12
+    }
 }
 
 impl Command for SbpCommand {
@@ -108,6 +154,7 @@ impl AddressCommand for SbpCommand {
         Some(SbpCommand {
             address: address.try_into().ok()?,
             command: command.try_into().ok()?,
+            unknown: 0,
             valid: true,
         })
     }
@@ -197,32 +244,6 @@ impl From<SbpState> for State {
     }
 }
 
-/*
-struct SbpTiming {
-    /// Header high
-    hh: u32,
-    /// Header low
-    hl: u32,
-    /// Repeat low
-    pause: u32,
-    /// Data high
-    data: u32,
-    /// Zero low
-    zero: u32,
-    /// One low
-    one: u32,
-}
-
-const TIMING: SbpTiming = SbpTiming {
-    hh: 4500,
-    hl: 4500,
-    pause: 4500,
-    data: 500,
-    zero: 500,
-    one: 1500,
-};
-*/
-
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SbpPulse {
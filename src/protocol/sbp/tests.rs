@@ -0,0 +1,112 @@
+use crate::{
+    cmd::AddressCommand,
+    protocol::{sbp::SbpCommand, Sbp},
+    receiver::BufferInputReceiver,
+    sender::PulsedataBuffer,
+};
+
+#[test]
+fn cmd_pack_unpack() {
+    let cmd = SbpCommand {
+        address: 32,
+        command: 2,
+        unknown: 0b1010,
+        valid: true,
+    };
+
+    assert_eq!(cmd.pack(), 0xFD02A);
+    assert_eq!(SbpCommand::unpack(32, cmd.pack()), cmd);
+
+    // Bad checksum
+    assert!(!SbpCommand::unpack(32, 0x0002A).valid);
+}
+
+#[test]
+fn all_commands() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+
+    for address in [0, 32, 0x1234, 0xFFFF] {
+        for cmdnum in 0..=255 {
+            ptb.reset();
+            let cmd = SbpCommand::create(address, cmdnum).unwrap();
+            ptb.load::<Sbp, FREQ>(&cmd);
+
+            let mut brecv = BufferInputReceiver::<Sbp>::with_frequenzy(FREQ);
+            let cmdres = brecv.iter(ptb.buffer()).next();
+
+            assert_eq!(cmdres, Some(cmd));
+        }
+    }
+}
+
+#[test]
+fn unknown_bits() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+
+    for unknown in 0..16 {
+        ptb.reset();
+        let cmd = SbpCommand {
+            address: 32,
+            command: 7,
+            unknown,
+            valid: true,
+        };
+        ptb.load::<Sbp, FREQ>(&cmd);
+
+        let mut brecv = BufferInputReceiver::<Sbp>::with_frequenzy(FREQ);
+
+        assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+    }
+}
+
+#[test]
+fn clock_frequencies() {
+    one_freq::<20_000>();
+    one_freq::<40_000>();
+    one_freq::<48_000_000>();
+}
+
+fn one_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<128>::new();
+    let cmd = SbpCommand::create(32, 10).unwrap();
+    ptb.load::<Sbp, F>(&cmd);
+
+    let mut brecv = BufferInputReceiver::<Sbp>::with_frequenzy(F);
+
+    assert_eq!(
+        brecv.iter(ptb.buffer()).next(),
+        Some(cmd),
+        "Samplerate: {}",
+        F
+    );
+}
+
+#[test]
+fn soundbar_remote() {
+    use crate::{
+        protocol::Samsung36,
+        remotecontrol::{sbp::SamsungSoundbar, Action, RemoteControlModel},
+    };
+
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+
+    for action in [
+        Action::Power,
+        Action::VolumeUp,
+        Action::VolumeMute,
+        Action::Setup,
+    ] {
+        ptb.reset();
+        let cmd = SamsungSoundbar::encode(&action).unwrap();
+        ptb.load::<Samsung36, FREQ>(&cmd);
+
+        let mut brecv = BufferInputReceiver::<Samsung36>::with_frequenzy(FREQ);
+        let cmdres = brecv.iter(ptb.buffer()).next().unwrap();
+
+        assert_eq!(cmdres.address, 0x5343);
+        assert_eq!(SamsungSoundbar::decode(&cmdres), Some(action));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    protocol::{
+        sharp::{SharpCommand, CHECK_BIT, DATA_HIGH, FRAME_GAP, NBITS, ONE_LOW, ZERO_LOW},
+        Sharp,
+    },
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecoderBuilder, DecodingError, ProtocolDecoder, State,
+    },
+};
+
+const PULSE: [u32; 8] = [
+    DATA_HIGH,
+    (DATA_HIGH + ZERO_LOW),
+    (DATA_HIGH + ONE_LOW),
+    FRAME_GAP,
+    0,
+    0,
+    0,
+    0,
+];
+const TOL: [u32; 8] = [40, 20, 20, 40, 0, 0, 0, 0];
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Sharp {
+    type Decoder = SharpDecoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        SharpDecoder {
+            state: SharpState::Idle,
+            bitbuf: 0,
+            dt_save: Mono::ZERO_DURATION,
+            spans: PulseSpans::new(freq, &PULSE, &TOL),
+        }
+    }
+}
+
+pub struct SharpDecoder<Mono: InfraMonotonic> {
+    state: SharpState,
+    /// First frame in bit 0 - 14 and the second frame in bit 15 - 29
+    bitbuf: u32,
+    dt_save: Mono::Duration,
+    spans: PulseSpans<Mono>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SharpState {
+    Idle,
+    /// Number of bits received, in both frames
+    Data(u8),
+    /// First frame received, waiting for the pause
+    Pause,
+    Done,
+    Err(DecodingError),
+}
+
+impl From<SharpState> for State {
+    fn from(state: SharpState) -> Self {
+        match state {
+            SharpState::Idle => State::Idle,
+            SharpState::Data(_) | SharpState::Pause => State::Receiving,
+            SharpState::Done => State::Done,
+            SharpState::Err(err) => State::Error(err),
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic> ProtocolDecoder<Sharp, Mono> for SharpDecoder<Mono> {
+    #[rustfmt::skip]
+    fn event(&mut self, rising: bool, dt: Mono::Duration) -> State {
+        use SharpPulse::*;
+        use SharpState::*;
+
+        const LAST: u8 = 2 * NBITS as u8 - 1;
+        const FIRST_LAST: u8 = NBITS as u8 - 1;
+
+        if rising {
+            let mark = self.spans.get::<SharpPulse>(self.dt_save).unwrap_or(Invalid);
+            let space = self.spans.get::<SharpPulse>(dt).unwrap_or(Invalid);
+            let total = self.spans.get::<SharpPulse>(self.dt_save + dt).unwrap_or(Invalid);
+
+            self.state = match (self.state, mark, space, total) {
+                // No header, start at the first mark
+                (Idle,      _,      _,      _)      => { self.bitbuf = 0; Data(0) }
+
+                (Data(bit @ (FIRST_LAST | LAST)), _, _, One) => { self.bitbuf |= 1 << bit; self.frame_done(bit) }
+                (Data(bit @ (FIRST_LAST | LAST)), _, _, Zero) => self.frame_done(bit),
+                (Data(bit), _,      _,      One)    => { self.bitbuf |= 1 << bit; Data(bit + 1) }
+                (Data(bit), _,      _,      Zero)   => Data(bit + 1),
+                (Data(_),   _,      _,      _)      => Err(DecodingError::Data),
+
+                // Stop mark and the pause before the inverted frame
+                (Pause,     Short,  Gap,    _)      => Data(NBITS as u8),
+                // The second frame never came, this could be the start of a new frame
+                (Pause,     Short,  Invalid, _)     => { self.bitbuf = 0; Data(0) }
+                (Pause,     _,      _,      _)      => Err(DecodingError::Data),
+
+                (Done,      _,      _,      _)      => Done,
+                (Err(err),  _,      _,      _)      => Err(err),
+            };
+
+            self.dt_save = Mono::ZERO_DURATION;
+        } else {
+            self.dt_save = dt;
+        }
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<SharpCommand> {
+        match self.state {
+            SharpState::Done => Some(SharpCommand::unpack(self.first())),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = SharpState::Idle;
+        self.bitbuf = 0;
+        self.dt_save = Mono::ZERO_DURATION;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.spans
+    }
+}
+
+impl<Mono: InfraMonotonic> SharpDecoder<Mono> {
+    fn first(&self) -> u16 {
+        (self.bitbuf & 0x7FFF) as u16
+    }
+
+    fn second(&self) -> u16 {
+        (self.bitbuf >> NBITS) as u16
+    }
+
+    /// Last bit of a frame received
+    fn frame_done(&mut self, bit: u8) -> SharpState {
+        if u32::from(bit) < NBITS {
+            SharpState::Pause
+        } else if SharpCommand::validate(self.first(), self.second()) {
+            SharpState::Done
+        } else if self.first() & CHECK_BIT != 0 && self.second() & CHECK_BIT == 0 {
+            // Started on an inverted frame, use the second frame as the first
+            self.bitbuf >>= NBITS;
+            SharpState::Pause
+        } else {
+            SharpState::Err(DecodingError::Validation)
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum SharpPulse {
+    /// Data or stop mark
+    Short,
+    Zero,
+    One,
+    /// Pause between the two frames
+    Gap,
+    Invalid,
+}
+
+impl From<usize> for SharpPulse {
+    fn from(v: usize) -> Self {
+        match v {
+            0 => SharpPulse::Short,
+            1 => SharpPulse::Zero,
+            2 => SharpPulse::One,
+            3 => SharpPulse::Gap,
+            _ => SharpPulse::Invalid,
+        }
+    }
+}
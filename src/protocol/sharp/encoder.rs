@@ -0,0 +1,48 @@
+use crate::{
+    protocol::{
+        sharp::{DATA_HIGH, FRAME_GAP, NBITS, ONE_LOW, ZERO_LOW},
+        utils::scale_with_samplerate,
+        Sharp,
+    },
+    sender::ProtocolEncoder,
+};
+
+impl<const F: u32> ProtocolEncoder<F> for Sharp {
+    type EncoderData = [u32; 4];
+    const DATA: Self::EncoderData = [
+        scale_with_samplerate(DATA_HIGH, F),
+        scale_with_samplerate(ZERO_LOW, F),
+        scale_with_samplerate(ONE_LOW, F),
+        scale_with_samplerate(FRAME_GAP, F),
+    ];
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        let [data_high, zero_low, one_low, gap] = <Self as ProtocolEncoder<F>>::DATA;
+
+        b[0] = 0;
+        let mut bi = 1;
+
+        for (i, bits) in [cmd.pack(), cmd.pack_inverted()].into_iter().enumerate() {
+            if i == 1 {
+                b[bi] = gap;
+                bi += 1;
+            }
+
+            for n in 0..NBITS {
+                b[bi] = data_high;
+                b[bi + 1] = if (bits >> n) & 1 != 0 {
+                    one_low
+                } else {
+                    zero_low
+                };
+                bi += 2;
+            }
+
+            // Stop mark
+            b[bi] = data_high;
+            bi += 1;
+        }
+
+        bi
+    }
+}
@@ -0,0 +1,125 @@
+//! Sharp
+//!
+//! Pulse distance coded protocol without header. A frame is 15 bits, LSB first, 5 bit address,
+//! 8 bit command, the expansion bit and the check bit, followed by a stop mark.
+//!
+//! Every frame is followed, after a 40 ms pause, by a second frame with the same address but
+//! with the command, expansion and check bits inverted. The decoder only reports a command
+//! when both frames have been received.
+
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::Protocol,
+    ProtocolId,
+};
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+const DATA_HIGH: u32 = 320;
+const ZERO_LOW: u32 = 680;
+const ONE_LOW: u32 = 1680;
+/// Pause between the stop mark of the first frame and the second frame
+const FRAME_GAP: u32 = 40_000;
+
+/// Number of bits in a frame
+const NBITS: u32 = 15;
+/// Bits inverted in the second frame
+const INVERTED_MASK: u16 = 0b_0111_1111_1110_0000;
+/// Check bit, cleared in the first frame
+const CHECK_BIT: u16 = 1 << 14;
+
+/// Sharp
+pub struct Sharp;
+
+impl Protocol for Sharp {
+    type Cmd = SharpCommand;
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SharpCommand {
+    /// Address, 5 bits
+    pub addr: u8,
+    pub cmd: u8,
+    /// Expansion bit, normally set
+    pub expansion: bool,
+    /// Check bit. Cleared in the first frame and set in the inverted second frame
+    pub check: bool,
+}
+
+impl SharpCommand {
+    pub const fn new(addr: u8, cmd: u8) -> Self {
+        Self {
+            addr,
+            cmd,
+            expansion: true,
+            check: false,
+        }
+    }
+
+    /// Unpack the 15 bits of the first frame
+    pub const fn unpack(bits: u16) -> Self {
+        Self {
+            addr: (bits & 0x1F) as u8,
+            cmd: (bits >> 5) as u8,
+            expansion: bits & (1 << 13) != 0,
+            check: bits & CHECK_BIT != 0,
+        }
+    }
+
+    /// The 15 bits of the first frame
+    pub fn pack(&self) -> u16 {
+        u16::from(self.addr & 0x1F)
+            | u16::from(self.cmd) << 5
+            | u16::from(self.expansion) << 13
+            | u16::from(self.check) << 14
+    }
+
+    /// The 15 bits of the second frame
+    pub fn pack_inverted(&self) -> u16 {
+        self.pack() ^ INVERTED_MASK
+    }
+
+    /// Check that `first` is a first frame and `second` its inverted frame
+    pub const fn validate(first: u16, second: u16) -> bool {
+        first & CHECK_BIT == 0 && first ^ second == INVERTED_MASK
+    }
+}
+
+impl Command for SharpCommand {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+}
+
+impl AddressCommand for SharpCommand {
+    const ID: ProtocolId = ProtocolId::Sharp;
+
+    fn raw(&self) -> u64 {
+        self.pack().into()
+    }
+
+    fn address(&self) -> u32 {
+        self.addr.into()
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        let addr: u8 = addr.try_into().ok()?;
+
+        if addr > 0x1F {
+            return None;
+        }
+
+        Some(SharpCommand::new(addr, cmd.try_into().ok()?))
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    cmd::AddressCommand,
+    protocol::{sharp::SharpCommand, Sharp},
+    receiver::{BufferInputReceiver, DecodingError},
+    sender::PulsedataBuffer,
+    Receiver,
+};
+
+#[test]
+fn cmd_pack_unpack() {
+    let cmd = SharpCommand::new(0x01, 0x8C);
+    assert_eq!(cmd.pack(), 0x2000 | 0x8C << 5 | 0x01);
+    assert_eq!(cmd.pack_inverted(), 0x4000 | 0x73 << 5 | 0x01);
+    assert_eq!(SharpCommand::unpack(cmd.pack()), cmd);
+    assert!(SharpCommand::validate(cmd.pack(), cmd.pack_inverted()));
+    assert!(!SharpCommand::validate(cmd.pack(), cmd.pack()));
+    assert!(!SharpCommand::validate(cmd.pack_inverted(), cmd.pack()));
+
+    assert_eq!(
+        SharpCommand::create(0x1F, 0xFF),
+        Some(SharpCommand::new(0x1F, 0xFF))
+    );
+    assert_eq!(SharpCommand::create(0x20, 0x01), None);
+}
+
+#[test]
+fn all_commands() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<96>::new();
+
+    for address in 0..32 {
+        for cmdnum in 0..255 {
+            ptb.reset();
+            let cmd = SharpCommand::new(address, cmdnum);
+            ptb.load::<Sharp, FREQ>(&cmd);
+
+            let mut brecv = BufferInputReceiver::<Sharp>::with_frequenzy(FREQ);
+            let cmdres = brecv.iter(ptb.buffer()).next().unwrap();
+
+            assert_eq!(cmd, cmdres);
+        }
+    }
+}
+
+#[test]
+fn second_frame_mismatch() {
+    const FREQ: u32 = 1_000_000;
+    let mut ptb = PulsedataBuffer::<96>::new();
+    ptb.load::<Sharp, FREQ>(&SharpCommand::new(0x01, 0x8C));
+
+    // Turn the first command bit of the inverted frame from a one into a zero
+    let mut buf = std::vec::Vec::from(ptb.buffer());
+    assert_eq!(buf[44], 1680);
+    buf[44] = 680;
+
+    let mut recv = Receiver::<Sharp>::new(FREQ);
+    let mut cmds = std::vec::Vec::new();
+    let mut errors = std::vec::Vec::new();
+
+    let mut edge = false;
+    for dt in buf {
+        edge = !edge;
+        match recv.event(dt, edge) {
+            Ok(Some(cmd)) => cmds.push(cmd),
+            Ok(None) => (),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    assert!(cmds.is_empty());
+    assert_eq!(errors, [DecodingError::Validation]);
+}
+
+#[test]
+fn missing_second_frame() {
+    const FREQ: u32 = 1_000_000;
+    let mut ptb = PulsedataBuffer::<96>::new();
+    let cmd = SharpCommand::new(0x03, 0x41);
+    ptb.load::<Sharp, FREQ>(&cmd);
+
+    // Only the first frame, followed by a complete pair
+    let mut buf = std::vec::Vec::from(&ptb.buffer()[..32]);
+    buf.push(200_000);
+    buf.extend_from_slice(&ptb.buffer()[1..]);
+
+    let mut brecv = BufferInputReceiver::<Sharp>::with_frequenzy(FREQ);
+    let cmds = brecv.iter(&buf).collect::<std::vec::Vec<_>>();
+
+    assert_eq!(cmds, [cmd]);
+}
+
+#[test]
+fn start_on_inverted_frame() {
+    const FREQ: u32 = 1_000_000;
+    let mut ptb = PulsedataBuffer::<96>::new();
+    let cmd = SharpCommand::new(0x05, 0x3A);
+    ptb.load::<Sharp, FREQ>(&cmd);
+
+    // The inverted frame and the gap, followed by a complete pair
+    let mut buf = std::vec::Vec::from(&ptb.buffer()[..1]);
+    buf.extend_from_slice(&ptb.buffer()[33..]);
+    buf.push(ptb.buffer()[32]);
+    buf.extend_from_slice(&ptb.buffer()[1..]);
+
+    let mut brecv = BufferInputReceiver::<Sharp>::with_frequenzy(FREQ);
+    let cmds = brecv.iter(&buf).collect::<std::vec::Vec<_>>();
+
+    assert_eq!(cmds, [cmd]);
+}
+
+#[test]
+fn clock_frequencies() {
+    one_freq::<20_000>();
+    one_freq::<40_000>();
+    one_freq::<48_000_000>();
+}
+
+fn one_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<96>::new();
+    let cmd = SharpCommand::new(0x11, 0xA5);
+    ptb.load::<Sharp, F>(&cmd);
+
+    let mut brecv = BufferInputReceiver::<Sharp>::with_frequenzy(F);
+
+    assert_eq!(
+        brecv.iter(ptb.buffer()).next(),
+        Some(cmd),
+        "Samplerate: {}",
+        F
+    );
+}
@@ -0,0 +1,19 @@
+//! Sirc decoder. Sirc is decoded by the generic pulse width decoder
+
+use crate::{
+    protocol::{pulsewidth::PulseWidthDecoder, Sirc},
+    receiver::{time::InfraMonotonic, DecoderBuilder},
+};
+
+pub use crate::protocol::pulsewidth::PulseWidthState as SircState;
+
+/// Sirc decoder
+pub type SircDecoder<Mono> = PulseWidthDecoder<Mono, Sirc>;
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Sirc {
+    type Decoder = SircDecoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        PulseWidthDecoder::new(freq)
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{
+    protocol::{
+        pulsewidth::encoder::{encode, encoder_data},
+        utils::scale_with_samplerate,
+        Sirc,
+    },
+    sender::ProtocolEncoder,
+};
+
+impl<const F: u32> ProtocolEncoder<F> for Sirc {
+    // Pulse lengths and the frame period
+    type EncoderData = ([u32; 6], u32);
+    const DATA: Self::EncoderData = (
+        encoder_data(&super::SIRC_PULSEWIDTH, F),
+        scale_with_samplerate(45_000, F),
+    );
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        let (data, period) = <Self as ProtocolEncoder<F>>::DATA;
+
+        let len = encode::<Self>(&data, cmd, b);
+        let frame_len: u32 = b[..len].iter().sum();
+
+        // Stretch the last space to the frame period, the receiver needs it to find the end of frame
+        b[len] = period.saturating_sub(frame_len);
+
+        len + 1
+    }
+}
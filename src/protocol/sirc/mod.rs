@@ -0,0 +1,187 @@
+//! Sony SIRC
+//!
+//! Pulse width coded protocol, decoded and encoded with the generic pulse width engine. The
+//! header is a 2.4 ms mark, after which the bits are sent LSB first as a 1.2 ms (one) or 0.6 ms
+//! (zero) mark, each followed by a 0.6 ms space.
+//!
+//! The 7 bit command is followed by a 5 bit (12 bit variant), 8 bit (15 bit variant) or 5 bit
+//! address and 8 bit extended device field (20 bit variant).
+//!
+//! There is no stop bit, the decoder finds the length of a 12 or 15 bit frame when the space after
+//! the last bit turns out to be the gap to the next frame, or the pause after the last frame.
+//! The pause is only seen on the next edge, unless the receiver checks for it with
+//! `Receiver::timeout`. Remotes send each frame at least three times with a 45 ms period.
+
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::{
+        pulsedistance::BitOrder,
+        pulsewidth::{Coding, PulseWidth, PulseWidthProtocol},
+        Protocol,
+    },
+    ProtocolId,
+};
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+/// Sony SIRC
+pub struct Sirc;
+
+impl Protocol for Sirc {
+    type Cmd = SircCommand;
+}
+
+/// Timing of the longest, 20 bit, variant
+const SIRC_PULSEWIDTH: PulseWidth = PulseWidth {
+    header_high: 2400,
+    header_low: 600,
+    zero_high: 600,
+    one_high: 1200,
+    zero_low: 600,
+    one_low: 600,
+    nbits: 20,
+    order: BitOrder::Lsb,
+    coding: Coding::Mark,
+};
+
+impl PulseWidthProtocol for Sirc {
+    const DESCRIPTOR: &'static PulseWidth = &SIRC_PULSEWIDTH;
+
+    fn ends_early(_bits: u64, n: u8) -> bool {
+        matches!(n, 12 | 15)
+    }
+
+    fn unpack(bits: u64, nbits: u8) -> Option<SircCommand> {
+        SircCommand::unpack(bits as u32, nbits)
+    }
+
+    fn pack(cmd: &SircCommand) -> (u64, u8) {
+        let nbits = match cmd.bits {
+            15 | 20 => cmd.bits,
+            _ => 12,
+        };
+
+        (cmd.pack().into(), nbits)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SircCommand {
+    /// Device address. 5 bits, or 8 bits in the 15 bit variant
+    pub addr: u8,
+    /// Command. 7 bits
+    pub cmd: u8,
+    /// Extended device field, only used by the 20 bit variant
+    pub ext: u8,
+    /// Number of bits in the frame. 12, 15 or 20
+    pub bits: u8,
+}
+
+impl SircCommand {
+    /// Create a 12 bit command
+    pub const fn new(addr: u8, cmd: u8) -> Self {
+        Self {
+            addr,
+            cmd,
+            ext: 0,
+            bits: 12,
+        }
+    }
+
+    /// Create a 15 bit command
+    pub const fn new15(addr: u8, cmd: u8) -> Self {
+        Self {
+            addr,
+            cmd,
+            ext: 0,
+            bits: 15,
+        }
+    }
+
+    /// Create a 20 bit command
+    pub const fn new20(addr: u8, cmd: u8, ext: u8) -> Self {
+        Self {
+            addr,
+            cmd,
+            ext,
+            bits: 20,
+        }
+    }
+
+    /// Unpack a frame of `nbits` bits, returns `None` if the length isn't a SIRC variant
+    pub const fn unpack(bits: u32, nbits: u8) -> Option<Self> {
+        let cmd = (bits & 0x7F) as u8;
+
+        match nbits {
+            12 => Some(Self::new(((bits >> 7) & 0x1F) as u8, cmd)),
+            15 => Some(Self::new15(((bits >> 7) & 0xFF) as u8, cmd)),
+            20 => Some(Self::new20(
+                ((bits >> 7) & 0x1F) as u8,
+                cmd,
+                ((bits >> 12) & 0xFF) as u8,
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn pack(&self) -> u32 {
+        let cmd = u32::from(self.cmd & 0x7F);
+
+        match self.bits {
+            15 => cmd | u32::from(self.addr) << 7,
+            20 => cmd | u32::from(self.addr & 0x1F) << 7 | u32::from(self.ext) << 12,
+            _ => cmd | u32::from(self.addr & 0x1F) << 7,
+        }
+    }
+}
+
+impl Command for SircCommand {
+    fn is_repeat(&self) -> bool {
+        false
+    }
+}
+
+impl AddressCommand for SircCommand {
+    const ID: ProtocolId = ProtocolId::Sirc;
+
+    fn raw(&self) -> u64 {
+        self.pack().into()
+    }
+
+    /// Number of bits of the frame in bit 16 - 23, extended device field in bit 5 - 12 and
+    /// address in bit 0 - 4, or bit 0 - 7 in the 15 bit variant
+    fn address(&self) -> u32 {
+        u32::from(self.bits) << 16 | u32::from(self.addr) | u32::from(self.ext) << 5
+    }
+
+    fn command(&self) -> u32 {
+        self.cmd.into()
+    }
+
+    /// Create a command of the variant with the number of bits in bit 16 - 23 of the address.
+    /// Without it the variant is picked from the size of the address, addresses above 255 use
+    /// the 20 bit variant with the upper bits in the extended field.
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        let cmd: u8 = cmd.try_into().ok().filter(|cmd| *cmd < 0x80)?;
+        let bits = addr >> 16;
+        let addr = addr & 0xFFFF;
+
+        match (bits, addr) {
+            (0 | 12, 0..=0x1F) => Some(SircCommand::new(addr as u8, cmd)),
+            (0 | 15, 0..=0xFF) => Some(SircCommand::new15(addr as u8, cmd)),
+            (0 | 20, 0..=0x1FFF) => Some(SircCommand::new20(
+                (addr & 0x1F) as u8,
+                cmd,
+                (addr >> 5) as u8,
+            )),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{
+    cmd::AddressCommand,
+    protocol::{sirc::SircCommand, Sirc},
+    receiver::{BufferInputReceiver, Receiver},
+    sender::PulsedataBuffer,
+};
+
+#[test]
+fn cmd_pack_unpack() {
+    let cmd = SircCommand::new(1, 21);
+    assert_eq!(cmd.pack(), 0x095);
+    assert_eq!(SircCommand::unpack(cmd.pack(), 12), Some(cmd));
+
+    let cmd = SircCommand::new15(0x97, 0x45);
+    assert_eq!(SircCommand::unpack(cmd.pack(), 15), Some(cmd));
+
+    let cmd = SircCommand::new20(0x1A, 0x7F, 0xB3);
+    assert_eq!(SircCommand::unpack(cmd.pack(), 20), Some(cmd));
+
+    assert_eq!(SircCommand::unpack(0, 13), None);
+}
+
+#[test]
+fn cmd_create() {
+    assert_eq!(SircCommand::create(1, 21), Some(SircCommand::new(1, 21)));
+    assert_eq!(
+        SircCommand::create(0x97, 3),
+        Some(SircCommand::new15(0x97, 3))
+    );
+
+    let cmd = SircCommand::create(0x1234, 3).unwrap();
+    assert_eq!(cmd, SircCommand::new20(0x14, 3, 0x91));
+    assert_eq!(cmd.address(), 20 << 16 | 0x1234);
+
+    // The variant is kept
+    for cmd in [
+        SircCommand::new(0x1F, 3),
+        SircCommand::new15(0x01, 3),
+        SircCommand::new20(0x01, 3, 0x02),
+    ] {
+        assert_eq!(SircCommand::create(cmd.address(), cmd.command()), Some(cmd));
+    }
+
+    assert_eq!(SircCommand::create(0x2000, 3), None);
+    assert_eq!(SircCommand::create(12 << 16 | 0x20, 3), None);
+    assert_eq!(SircCommand::create(13 << 16 | 0x01, 3), None);
+    assert_eq!(SircCommand::create(1, 0x80), None);
+}
+
+#[test]
+#[rustfmt::skip]
+fn decode_buffer() {
+    // Sony TV power (address 1, command 21) at 40 kHz, with some receiver jitter
+    let dists = [
+        0, 97, 23,
+        50, 22, 26, 22, 51, 22, 26, 22, 51, 22, 26, 22, 26, 22,
+        49, 22, 26, 22, 26, 22, 26, 22, 26,
+        1030,
+        96, 23,
+        50, 22, 26, 22, 51, 22, 26, 22, 51, 22, 26, 22, 26, 22,
+        49, 22, 26, 22, 26, 22, 26, 22, 26,
+        1030,
+    ];
+
+    let mut brecv = BufferInputReceiver::<Sirc>::with_frequenzy(40_000);
+    let cmds = brecv.iter(&dists).collect::<std::vec::Vec<_>>();
+
+    assert_eq!(cmds.len(), 2);
+
+    for cmd in &cmds {
+        assert_eq!(cmd.addr, 1);
+        assert_eq!(cmd.cmd, 21);
+        assert_eq!(cmd.bits, 12);
+    }
+}
+
+#[test]
+fn all_commands() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<96>::new();
+
+    for cmdnum in 0..128 {
+        for (addr, ext) in [(0, 0), (31, 0), (200, 0), (17, 0xA5)] {
+            let cmd = match (addr, ext) {
+                (0..=31, 0) => SircCommand::new(addr, cmdnum),
+                (_, 0) => SircCommand::new15(addr, cmdnum),
+                _ => SircCommand::new20(addr, cmdnum, ext),
+            };
+
+            ptb.reset();
+            ptb.load::<Sirc, FREQ>(&cmd);
+
+            let mut brecv = BufferInputReceiver::<Sirc>::with_frequenzy(FREQ);
+            let cmdres = brecv.iter(ptb.buffer()).next().unwrap();
+
+            assert_eq!(cmd, cmdres);
+        }
+    }
+}
+
+#[test]
+fn last_frame() {
+    const FREQ: u32 = 1_000_000;
+    let mut ptb = PulsedataBuffer::<96>::new();
+    let cmd = SircCommand::new(1, 21);
+    ptb.load::<Sirc, FREQ>(&cmd);
+
+    // Replace the frame gap with a long pause
+    let mut buf = std::vec::Vec::from(ptb.buffer());
+    *buf.last_mut().unwrap() = 200_000;
+
+    let mut brecv = BufferInputReceiver::<Sirc>::with_frequenzy(FREQ);
+    assert_eq!(brecv.iter(&buf).next(), Some(cmd));
+
+    // Without the pause, the frame is completed by the timeout
+    buf.pop();
+    let mut recv = Receiver::<Sirc>::new(FREQ);
+    for (i, dt) in buf.iter().enumerate() {
+        assert_eq!(recv.event(*dt, i & 0x1 == 0), Ok(None));
+    }
+    assert_eq!(recv.timeout(1000), None);
+    assert_eq!(recv.timeout(5000), Some(cmd));
+}
+
+#[test]
+fn clock_frequencies() {
+    one_freq::<20_000>();
+    one_freq::<40_000>();
+    one_freq::<48_000_000>();
+}
+
+fn one_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<96>::new();
+    let cmd = SircCommand::new15(0x97, 0x45);
+    ptb.load::<Sirc, F>(&cmd);
+
+    let mut brecv = BufferInputReceiver::<Sirc>::with_frequenzy(F);
+
+    assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+}
@@ -0,0 +1,145 @@
+use crate::{
+    protocol::{
+        xmp::{XmpCommand, DATA_HIGH, NIBBLE_LOW, NIBBLE_STEP, PACKET_GAP},
+        Xmp,
+    },
+    receiver::{
+        time::{InfraMonotonic, PulseSpans},
+        DecoderBuilder, DecodingError, ProtocolDecoder, State,
+    },
+};
+
+const MARK: usize = 0;
+const GAP: usize = 1;
+
+const PULSE: [u32; 8] = [DATA_HIGH, PACKET_GAP, 0, 0, 0, 0, 0, 0];
+const TOL: [u32; 8] = [50, 20, 0, 0, 0, 0, 0, 0];
+
+/// Mark and space of the nibbles
+const NIBBLES: [u32; 16] = {
+    let mut pulse = [0; 16];
+    let mut n = 0;
+    while n < 16 {
+        pulse[n] = DATA_HIGH + NIBBLE_LOW + n as u32 * NIBBLE_STEP;
+        n += 1;
+    }
+    pulse
+};
+
+/// Half a step, in percent of each nibble
+const NIBBLES_TOL: [u32; 16] = {
+    let mut tol = [0; 16];
+    let mut n = 0;
+    while n < 16 {
+        tol[n] = NIBBLE_STEP * 50 / NIBBLES[n];
+        n += 1;
+    }
+    tol
+};
+
+impl<Mono: InfraMonotonic> DecoderBuilder<Mono> for Xmp {
+    type Decoder = XmpDecoder<Mono>;
+
+    fn build(freq: u32) -> Self::Decoder {
+        XmpDecoder {
+            state: XmpState::Idle,
+            bitbuf: 0,
+            dt_save: Mono::ZERO_DURATION,
+            spans: PulseSpans::new(freq, &PULSE, &TOL),
+            nibbles: PulseSpans::new(freq, &NIBBLES, &NIBBLES_TOL),
+        }
+    }
+}
+
+pub struct XmpDecoder<Mono: InfraMonotonic> {
+    state: XmpState,
+    /// The first packet in the high 32 bits
+    bitbuf: u64,
+    dt_save: Mono::Duration,
+    spans: PulseSpans<Mono>,
+    nibbles: PulseSpans<Mono, 16>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum XmpState {
+    Idle,
+    /// Number of nibbles received, in both packets
+    Nibble(u8),
+    /// First packet received, waiting for the stop mark and the gap
+    Gap,
+    Done,
+    Err(DecodingError),
+}
+
+impl From<XmpState> for State {
+    fn from(state: XmpState) -> Self {
+        match state {
+            XmpState::Idle => State::Idle,
+            XmpState::Nibble(_) | XmpState::Gap => State::Receiving,
+            XmpState::Done => State::Done,
+            XmpState::Err(err) => State::Error(err),
+        }
+    }
+}
+
+impl<Mono: InfraMonotonic> ProtocolDecoder<Xmp, Mono> for XmpDecoder<Mono> {
+    #[rustfmt::skip]
+    fn event(&mut self, rising: bool, dt: Mono::Duration) -> State {
+        use XmpState::*;
+
+        if rising {
+            let mark = self.spans.matches(MARK, self.dt_save);
+            let nibble = self.nibbles.get::<usize>(self.dt_save + dt).filter(|_| mark);
+
+            self.state = match (self.state, nibble) {
+                // No header, the frame starts with a nibble
+                (Idle,          Some(v))    => { self.bitbuf = 0; self.store(0, v); Nibble(1) }
+                (Idle,          None)       => Idle,
+
+                (Nibble(7),     Some(v))    => { self.store(7, v); Gap }
+                (Nibble(15),    Some(v))    => {
+                    self.store(15, v);
+                    if XmpCommand::unpack(self.bitbuf).is_some() { Done } else { Err(DecodingError::Validation) }
+                }
+                (Nibble(n),     Some(v))    => { self.store(n, v); Nibble(n + 1) }
+                (Nibble(_),     None)       => Err(DecodingError::Data),
+
+                (Gap,           _) if mark && self.spans.matches(GAP, dt) => Nibble(8),
+                (Gap,           _)          => Err(DecodingError::Data),
+
+                (Done,          _)          => Done,
+                (Err(err),      _)          => Err(err),
+            };
+
+            self.dt_save = Mono::ZERO_DURATION;
+        } else {
+            self.dt_save = dt;
+        }
+
+        self.state.into()
+    }
+
+    fn command(&self) -> Option<XmpCommand> {
+        match self.state {
+            XmpState::Done => XmpCommand::unpack(self.bitbuf),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = XmpState::Idle;
+        self.bitbuf = 0;
+        self.dt_save = Mono::ZERO_DURATION;
+    }
+
+    fn spans(&self) -> &PulseSpans<Mono> {
+        &self.spans
+    }
+}
+
+impl<Mono: InfraMonotonic> XmpDecoder<Mono> {
+    fn store(&mut self, n: u8, value: usize) {
+        self.bitbuf |= (value as u64) << (60 - 4 * u32::from(n));
+    }
+}
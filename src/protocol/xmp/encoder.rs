@@ -0,0 +1,52 @@
+use crate::{
+    protocol::{
+        utils::scale_with_samplerate,
+        xmp::{DATA_HIGH, NIBBLE_LOW, NIBBLE_STEP, PACKET_GAP},
+        Xmp,
+    },
+    sender::ProtocolEncoder,
+};
+
+impl<const F: u32> ProtocolEncoder<F> for Xmp {
+    /// Data mark, packet gap and the spaces of the 16 nibbles
+    type EncoderData = [u32; 18];
+    const DATA: Self::EncoderData = {
+        let mut data = [0; 18];
+        data[0] = scale_with_samplerate(DATA_HIGH, F);
+        data[1] = scale_with_samplerate(PACKET_GAP, F);
+
+        let mut n = 0;
+        while n < 16 {
+            data[2 + n] = scale_with_samplerate(NIBBLE_LOW + n as u32 * NIBBLE_STEP, F);
+            n += 1;
+        }
+        data
+    };
+
+    fn encode(cmd: &Self::Cmd, b: &mut [u32]) -> usize {
+        let data = <Self as ProtocolEncoder<F>>::DATA;
+        let (data_high, gap, nibbles) = (data[0], data[1], &data[2..]);
+
+        let bits = cmd.pack();
+
+        b[0] = 0;
+        let mut bi = 1;
+
+        for n in 0..16 {
+            if n == 8 {
+                // Stop mark of the first packet and the gap
+                b[bi] = data_high;
+                b[bi + 1] = gap;
+                bi += 2;
+            }
+
+            b[bi] = data_high;
+            b[bi + 1] = nibbles[(bits >> (60 - 4 * n) & 0xF) as usize];
+            bi += 2;
+        }
+
+        // Stop mark
+        b[bi] = data_high;
+        bi + 1
+    }
+}
@@ -0,0 +1,158 @@
+//! Microsoft XMP
+//!
+//! Used by the Xbox One and many Motorola and Comcast set top boxes. Every mark is followed by
+//! one of 16 space lengths, each coding a nibble, MSB first. A frame is two packets of eight
+//! nibbles, separated by a gap and each ending with a stop mark:
+//!
+//!  - Sub-device high nibble, checksum, sub-device low nibble, `F`, OEM code (8 bits), device
+//!  - Sub-device high nibble, checksum, toggle, sub-device low nibble, function (16 bits)
+//!
+//! The nibbles of each packet, including the checksum, sum to zero modulo 16. The toggle
+//! nibble is 0 in the first frame and 8 in the repeats.
+
+use core::convert::TryInto;
+
+use crate::{
+    cmd::{AddressCommand, Command},
+    protocol::Protocol,
+    ProtocolId,
+};
+
+pub mod decoder;
+pub mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+const DATA_HIGH: u32 = 210;
+/// Space of the zero nibble
+const NIBBLE_LOW: u32 = 760;
+/// Space added for each step of the nibble value
+const NIBBLE_STEP: u32 = 136;
+/// Space between the stop mark of the first packet and the second packet
+const PACKET_GAP: u32 = 13_800;
+
+const TOGGLE: u8 = 8;
+
+/// XMP
+pub struct Xmp;
+
+impl Protocol for Xmp {
+    type Cmd = XmpCommand;
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct XmpCommand {
+    pub sub_device: u8,
+    pub oem: u8,
+    pub device: u8,
+    pub function: u16,
+    /// Repeat frame, toggle nibble set
+    pub repeat: bool,
+}
+
+/// Checksum nibble making the nibbles of `packet` sum to zero
+const fn checksum(packet: u32) -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 8 {
+        sum += (packet >> (4 * i)) & 0xF;
+        i += 1;
+    }
+    sum.wrapping_neg() & 0xF
+}
+
+/// Sum of the nibbles is zero modulo 16
+fn valid(packet: u32) -> bool {
+    (0..8).map(|i| packet >> (4 * i) & 0xF).sum::<u32>() & 0xF == 0
+}
+
+impl XmpCommand {
+    pub const fn new(oem: u8, device: u8, sub_device: u8, function: u16) -> Self {
+        Self {
+            sub_device,
+            oem,
+            device,
+            function,
+            repeat: false,
+        }
+    }
+
+    /// Unpack a frame, the first packet in the high 32 bits
+    pub fn unpack(bits: u64) -> Option<Self> {
+        let first = (bits >> 32) as u32;
+        let second = bits as u32;
+
+        let nibble = |packet: u32, n: u32| (packet >> (28 - 4 * n) & 0xF) as u8;
+        let sub_device = nibble(first, 0) << 4 | nibble(first, 2);
+
+        let fixed = nibble(first, 3) == 0xF
+            && nibble(second, 0) << 4 | nibble(second, 3) == sub_device
+            && nibble(second, 2) & !TOGGLE == 0;
+
+        if !(fixed && valid(first) && valid(second)) {
+            return None;
+        }
+
+        Some(XmpCommand {
+            sub_device,
+            oem: (first >> 8) as u8,
+            device: first as u8,
+            function: second as u16,
+            repeat: nibble(second, 2) == TOGGLE,
+        })
+    }
+
+    /// Pack the command into the two packets, the first in the high 32 bits
+    pub fn pack(&self) -> u64 {
+        let s_hi = u32::from(self.sub_device >> 4);
+        let s_lo = u32::from(self.sub_device & 0xF);
+        let toggle = if self.repeat { u32::from(TOGGLE) } else { 0 };
+
+        let first =
+            s_hi << 28 | s_lo << 20 | 0xF << 16 | u32::from(self.oem) << 8 | u32::from(self.device);
+        let second = s_hi << 28 | toggle << 20 | s_lo << 16 | u32::from(self.function);
+
+        let first = first | checksum(first) << 24;
+        let second = second | checksum(second) << 24;
+
+        u64::from(first) << 32 | u64::from(second)
+    }
+}
+
+impl Command for XmpCommand {
+    fn is_repeat(&self) -> bool {
+        self.repeat
+    }
+}
+
+impl AddressCommand for XmpCommand {
+    const ID: ProtocolId = ProtocolId::Xmp;
+
+    fn raw(&self) -> u64 {
+        self.pack()
+    }
+
+    /// OEM code in bit 16 - 23, device in bit 8 - 15 and sub-device in bit 0 - 7
+    fn address(&self) -> u32 {
+        u32::from(self.oem) << 16 | u32::from(self.device) << 8 | u32::from(self.sub_device)
+    }
+
+    fn command(&self) -> u32 {
+        self.function.into()
+    }
+
+    fn create(addr: u32, cmd: u32) -> Option<Self> {
+        if addr > 0xFF_FFFF {
+            return None;
+        }
+
+        Some(XmpCommand::new(
+            (addr >> 16) as u8,
+            (addr >> 8) as u8,
+            addr as u8,
+            cmd.try_into().ok()?,
+        ))
+    }
+}
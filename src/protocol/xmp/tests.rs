@@ -0,0 +1,132 @@
+use crate::{
+    cmd::AddressCommand,
+    protocol::{xmp::XmpCommand, Xmp},
+    receiver::BufferInputReceiver,
+    sender::PulsedataBuffer,
+};
+
+#[test]
+fn cmd_pack_unpack() {
+    let cmd = XmpCommand::new(0x44, 0x15, 0x0E, 0x2F01);
+    let bits = cmd.pack();
+
+    assert_eq!(bits, 0x05EF_4415_000E_2F01);
+    assert_eq!(XmpCommand::unpack(bits), Some(cmd));
+
+    let repeat = XmpCommand {
+        repeat: true,
+        ..cmd
+    };
+    assert_eq!(repeat.pack(), 0x05EF_4415_088E_2F01);
+    assert_eq!(XmpCommand::unpack(repeat.pack()), Some(repeat));
+
+    // Checksums
+    assert_eq!(XmpCommand::unpack(bits ^ 1 << 40), None);
+    assert_eq!(XmpCommand::unpack(bits ^ 1 << 4), None);
+    // Sub-device differs between the packets
+    let other = XmpCommand::new(0x44, 0x15, 0x0F, 0x2F01).pack();
+    assert_eq!(
+        XmpCommand::unpack(bits & !0xFFFF_FFFF | other & 0xFFFF_FFFF),
+        None
+    );
+}
+
+#[test]
+fn cmd_create() {
+    let cmd = XmpCommand::create(0x44150E, 0x2F01).unwrap();
+    assert_eq!(cmd, XmpCommand::new(0x44, 0x15, 0x0E, 0x2F01));
+    assert_eq!(cmd.address(), 0x44150E);
+    assert_eq!(cmd.command(), 0x2F01);
+
+    assert_eq!(XmpCommand::create(0x100_0000, 0), None);
+    assert_eq!(XmpCommand::create(0, 0x1_0000), None);
+}
+
+#[test]
+fn encode_frame() {
+    let mut ptb = PulsedataBuffer::<64>::new();
+    ptb.load::<Xmp, 1_000_000>(&XmpCommand::new(0x44, 0x15, 0x0E, 0x2F01));
+
+    let buf = ptb.buffer();
+    // Sub-device high nibble 0 and the checksum 5
+    assert_eq!(&buf[..5], &[0, 210, 760, 210, 760 + 5 * 136]);
+    // Stop mark and the gap between the packets
+    assert_eq!(&buf[17..19], &[210, 13_800]);
+    assert_eq!(buf.len(), 1 + 2 * 16 + 2 + 1);
+}
+
+#[test]
+fn all_functions() {
+    const FREQ: u32 = 40_000;
+    let mut ptb = PulsedataBuffer::<64>::new();
+
+    for function in (0..=0xFFFF).step_by(97) {
+        for (oem, device, sub_device) in [(0x44, 0x15, 0x0E), (0, 0, 0), (0xFF, 0xFF, 0xFF)] {
+            let cmd = XmpCommand {
+                repeat: function % 2 == 0,
+                ..XmpCommand::new(oem, device, sub_device, function)
+            };
+
+            ptb.reset();
+            ptb.load::<Xmp, FREQ>(&cmd);
+
+            let mut brecv = BufferInputReceiver::<Xmp>::with_frequenzy(FREQ);
+            assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+        }
+    }
+}
+
+#[test]
+fn bad_checksum() {
+    let mut ptb = PulsedataBuffer::<64>::new();
+    ptb.load::<Xmp, 1_000_000>(&XmpCommand::new(0x44, 0x15, 0x0E, 0x2F01));
+
+    // One step longer space in the last nibble
+    let mut buf = std::vec::Vec::from(ptb.buffer());
+    let last = buf.len() - 2;
+    buf[last] += 136;
+
+    let mut brecv = BufferInputReceiver::<Xmp>::with_frequenzy(1_000_000);
+    assert_eq!(brecv.iter(&buf).next(), None);
+}
+
+#[test]
+fn clock_frequencies() {
+    one_freq::<40_000>();
+    one_freq::<1_000_000>();
+    one_freq::<48_000_000>();
+}
+
+fn one_freq<const F: u32>() {
+    let mut ptb = PulsedataBuffer::<64>::new();
+    let cmd = XmpCommand::new(0x44, 0x15, 0x0E, 0xFEDC);
+    ptb.load::<Xmp, F>(&cmd);
+
+    let mut brecv = BufferInputReceiver::<Xmp>::with_frequenzy(F);
+
+    assert_eq!(brecv.iter(ptb.buffer()).next(), Some(cmd));
+}
+
+#[test]
+fn noise_is_idle() {
+    use crate::receiver::{DecoderBuilder, ProtocolDecoder, State};
+
+    let mut decoder = <Xmp as DecoderBuilder<u32>>::build(1_000_000);
+
+    // Marks and spaces that aren't nibbles
+    for (i, dt) in [0, 5000, 300, 40, 20_000, 3000].iter().enumerate() {
+        assert_eq!(decoder.event(i % 2 == 0, *dt), State::Idle);
+    }
+
+    // A frame after the noise
+    let cmd = XmpCommand::new(0x44, 0x15, 0x0E, 0x2F01);
+    let mut ptb = PulsedataBuffer::<64>::new();
+    ptb.load::<Xmp, 1_000_000>(&cmd);
+
+    let mut state = State::Idle;
+    for (i, dt) in ptb.buffer().iter().enumerate() {
+        state = decoder.event(i % 2 == 0, *dt);
+    }
+    assert_eq!(state, State::Done);
+    assert_eq!(decoder.command(), Some(cmd));
+}
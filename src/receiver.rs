@@ -115,21 +115,27 @@ use embedded_hal::digital::v2::InputPin;
 
 use crate::{receiver::time::InfraMonotonic, Protocol};
 
+mod auto;
 mod bufferinput;
 mod builder;
 mod decoder;
 mod error;
 mod iter;
+pub mod keys;
 mod multi;
 mod ppoll;
+pub mod raw;
 pub mod time;
 
+pub use auto::AutoReceiver;
 pub use bufferinput::BufferInputReceiver;
 pub use builder::Builder;
 pub use decoder::{DecoderBuilder, ProtocolDecoder, State};
 pub use error::{DecodingError, Error};
+pub use keys::{KeyEvent, KeyEvents};
 pub use multi::MultiReceiver;
 pub use ppoll::PeriodicPoll;
+pub use raw::{RawCapture, RawFrame};
 
 /// Don't use a embedded-hal pin as input
 pub struct NoPin;
@@ -206,6 +212,24 @@ where
             State::Idle | State::Receiving => Ok(None),
         }
     }
+
+    /// Notify the receiver that there has been no edge for `dt`. Completes the frames that
+    /// end with the pause after them, call it from a timer after the last edge
+    pub fn timeout(&mut self, dt: Mono::Duration) -> Option<Cmd> {
+        if !self.decoder.timeout(dt) {
+            return None;
+        }
+
+        let cmd = self.decoder.command().map(Into::into);
+        self.decoder.reset();
+        cmd
+    }
+
+    /// Notify the receiver that there has been no edge until `t`, see [`Receiver::timeout`]
+    pub fn timeout_instant(&mut self, t: Mono::Instant) -> Option<Cmd> {
+        let dt = Mono::checked_sub(t, self.prev_instant).unwrap_or(Mono::ZERO_DURATION);
+        self.timeout(dt)
+    }
 }
 
 #[cfg(feature = "embedded-hal")]
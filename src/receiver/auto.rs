@@ -0,0 +1,469 @@
+//! Receiver that detects the protocol
+//!
+//! Every edge is fed to the decoders of all compiled-in protocols. The first protocol to
+//! complete a valid frame wins, its `ProtocolId` is reported with the command and later frames
+//! are only fed to its decoder, until the receiver is unlocked.
+//!
+//! If several decoders complete the same frame, the protocol listed first wins, Denon frames are
+//! Kaseikyo frames with the Denon vendor id and are reported as Denon. A Jvc frame is
+//! the start of a Nec frame and a Grundig frame the start of a Nrc17 frame. Their commands are
+//! held back while a decoder listed before them is still receiving the same frame and reported
+//! when that decoder gives up, at the latest on the first edge after the pause.
+//!
+//! While detecting, every frame is an error for most of the decoders and errors are not
+//! reported. Once locked, the errors of the protocol decoder are reported.
+//!
+//! `NecExt` and `Nec42` are not detected, `NecExt` accepts every 32 bit Nec frame and the first
+//! 32 bits of a `Nec42` frame look like a Nec frame. The air conditioner protocols don't have
+//! an `AddressCommand` and are not detected either. `Hash` accepts every frame and isn't detected.
+
+use core::marker::PhantomData;
+
+#[cfg(feature = "embedded-hal")]
+use embedded_hal::digital::v2::InputPin;
+
+#[cfg(feature = "embedded-hal")]
+use crate::receiver::Error;
+
+use crate::{
+    cmd::{AddressCommand, AnyCommand},
+    receiver::{
+        time::InfraMonotonic, DecoderBuilder, DecodingError, NoPin, ProtocolDecoder, State,
+    },
+    Protocol, ProtocolId,
+};
+
+#[cfg(all(
+    test,
+    feature = "nec",
+    feature = "jvc",
+    feature = "denon",
+    feature = "kaseikyo",
+    feature = "sharp",
+    feature = "sbp",
+    feature = "sirc",
+    feature = "rc5",
+    feature = "rc6",
+    feature = "nrc17",
+    feature = "grundig",
+    feature = "rcmm",
+    feature = "xmp",
+    feature = "lego_pf",
+))]
+mod tests;
+
+/// Max number of decoders
+const MAX_DECODERS: usize = 32;
+/// Spaces this long end the frame for all decoders, in us
+const PAUSE: u32 = 20_000;
+
+/// Protocol detecting Receiver
+pub struct AutoReceiver<Input = NoPin, Mono: InfraMonotonic = u32> {
+    decoders: Decoders<Mono>,
+    /// The detected protocol
+    locked: Option<ProtocolId>,
+    /// Command held back while a decoder listed before it is receiving
+    pending: Option<Pending>,
+    /// Edge counter
+    edge: u32,
+    /// Edge where the decoders started receiving the current frame
+    starts: [Option<u32>; MAX_DECODERS],
+    pause: Mono::Duration,
+    input: Input,
+    prev_instant: Mono::Instant,
+}
+
+impl<Mono: InfraMonotonic> AutoReceiver<NoPin, Mono> {
+    pub fn new(freq: u32) -> Self {
+        Self::with_input(freq, NoPin)
+    }
+
+    pub fn event(
+        &mut self,
+        dt: Mono::Duration,
+        edge: bool,
+    ) -> Result<Option<AnyCommand>, DecodingError> {
+        self.event_edge(dt, edge)
+    }
+
+    pub fn event_instant(
+        &mut self,
+        t: Mono::Instant,
+        edge: bool,
+    ) -> Result<Option<AnyCommand>, DecodingError> {
+        let dt = Mono::checked_sub(t, self.prev_instant).unwrap_or(Mono::ZERO_DURATION);
+        self.prev_instant = t;
+
+        self.event_edge(dt, edge)
+    }
+}
+
+impl<Input, Mono: InfraMonotonic> AutoReceiver<Input, Mono> {
+    pub fn with_input(freq: u32, input: Input) -> Self {
+        AutoReceiver {
+            decoders: Decoders::new(freq),
+            locked: None,
+            pending: None,
+            edge: 0,
+            starts: [None; MAX_DECODERS],
+            pause: Mono::create_span(freq, PAUSE, 0).low(),
+            input,
+            prev_instant: Mono::ZERO_INSTANT,
+        }
+    }
+
+    /// The detected protocol, `None` while detecting
+    pub fn protocol(&self) -> Option<ProtocolId> {
+        self.locked
+    }
+
+    /// Only feed edges to the decoder of `protocol`
+    pub fn lock(&mut self, protocol: ProtocolId) {
+        self.locked = Some(protocol);
+        self.reset();
+    }
+
+    /// Go back to detecting the protocol
+    pub fn unlock(&mut self) {
+        self.locked = None;
+        self.reset();
+    }
+
+    fn reset(&mut self) {
+        self.pending = None;
+        self.starts = [None; MAX_DECODERS];
+        self.decoders.reset();
+    }
+
+    /// Feed an edge to the decoders
+    pub fn event_edge(
+        &mut self,
+        dt: Mono::Duration,
+        edge: bool,
+    ) -> Result<Option<AnyCommand>, DecodingError> {
+        self.edge = self.edge.wrapping_add(1);
+        let held = self.pending.is_some();
+
+        if edge && dt >= self.pause {
+            // Decoders still receiving after a pause are receiving a new frame
+            for start in self.starts.iter_mut().flatten() {
+                *start = self.edge;
+            }
+        }
+
+        let mut events = Events {
+            edge: self.edge,
+            index: 0,
+            starts: &mut self.starts,
+            pending: self.pending.take(),
+            error: None,
+        };
+        self.decoders.event(self.locked, edge, dt, &mut events);
+        let (pending, error) = (events.pending, events.error);
+
+        match pending {
+            Some(pending) if self.blocked(&pending) => {
+                self.pending = Some(pending);
+                Ok(None)
+            }
+            Some(pending) => {
+                if self.locked.is_none() {
+                    self.locked = Some(pending.cmd.protocol);
+                    self.reset();
+
+                    if held {
+                        // The edge might start the next frame
+                        let mut events = Events {
+                            edge: self.edge,
+                            index: 0,
+                            starts: &mut self.starts,
+                            pending: None,
+                            error: None,
+                        };
+                        self.decoders.event(self.locked, edge, dt, &mut events);
+                    }
+                }
+                Ok(Some(pending.cmd))
+            }
+            None => match error {
+                Some(err) if self.locked.is_some() => Err(err),
+                _ => Ok(None),
+            },
+        }
+    }
+
+    /// Notify the receiver that there has been no edge for `dt`, see [`Receiver::timeout`]
+    ///
+    /// [`Receiver::timeout`]: crate::Receiver::timeout
+    pub fn timeout(&mut self, dt: Mono::Duration) -> Option<AnyCommand> {
+        let mut events = Events {
+            edge: self.edge,
+            index: 0,
+            starts: &mut self.starts,
+            pending: self.pending.take(),
+            error: None,
+        };
+        self.decoders.timeout(self.locked, dt, &mut events);
+        let pending = events.pending?;
+
+        // No decoder listed before it completes the frame after the pause
+        if dt < self.pause && self.blocked(&pending) {
+            self.pending = Some(pending);
+            return None;
+        }
+
+        if self.locked.is_none() {
+            self.locked = Some(pending.cmd.protocol);
+            self.reset();
+        }
+        Some(pending.cmd)
+    }
+
+    /// Notify the receiver that there has been no edge until `t`, see [`AutoReceiver::timeout`]
+    pub fn timeout_instant(&mut self, t: Mono::Instant) -> Option<AnyCommand> {
+        let dt = Mono::checked_sub(t, self.prev_instant).unwrap_or(Mono::ZERO_DURATION);
+        self.timeout(dt)
+    }
+
+    /// The pending command is held back and a decoder listed before it is receiving the same
+    /// frame
+    fn blocked(&self, pending: &Pending) -> bool {
+        pending.hold
+            && self.starts[..pending.index]
+                .iter()
+                .flatten()
+                .any(|start| *start <= pending.start)
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<Pin: InputPin, Mono: InfraMonotonic> AutoReceiver<Pin, Mono> {
+    /// Create an `AutoReceiver` with `pin` as input
+    pub fn with_pin(resolution: u32, pin: Pin) -> Self {
+        Self::with_input(resolution, pin)
+    }
+
+    pub fn event(&mut self, dt: Mono::Duration) -> Result<Option<AnyCommand>, Error<Pin::Error>> {
+        let edge = self.input.is_low().map_err(Error::Hal)?;
+        Ok(self.event_edge(dt, edge)?)
+    }
+
+    pub fn event_instant(
+        &mut self,
+        t: Mono::Instant,
+    ) -> Result<Option<AnyCommand>, Error<Pin::Error>> {
+        let edge = self.input.is_low().map_err(Error::Hal)?;
+
+        let dt = Mono::checked_sub(t, self.prev_instant).unwrap_or(Mono::ZERO_DURATION);
+        self.prev_instant = t;
+
+        Ok(self.event_edge(dt, edge)?)
+    }
+
+    /// Get a reference to the Pin
+    pub fn pin(&self) -> &Pin {
+        &self.input
+    }
+
+    /// Drop the receiver and release the pin
+    pub fn release(self) -> Pin {
+        self.input
+    }
+}
+
+/// Completed command
+struct Pending {
+    /// Position of the decoder
+    index: usize,
+    /// Edge where the frame started
+    start: u32,
+    /// Hold back while a decoder listed before is receiving the same frame
+    hold: bool,
+    cmd: AnyCommand,
+}
+
+/// State of the decoders when feeding an edge
+// Only read by `decoder_event`, unused without a detected protocol
+#[allow(dead_code)]
+struct Events<'a> {
+    /// Edge counter
+    edge: u32,
+    /// Position of the current decoder
+    index: usize,
+    starts: &'a mut [Option<u32>; MAX_DECODERS],
+    /// Completed command of the first decoder
+    pending: Option<Pending>,
+    /// First error
+    error: Option<DecodingError>,
+}
+
+/// Feed the event to the decoder of `Proto`, unless another protocol is locked
+#[allow(dead_code)]
+fn decoder_event<Proto, Mono, const HOLD: bool>(
+    decoder: &mut Proto::Decoder,
+    locked: Option<ProtocolId>,
+    edge: bool,
+    dt: Mono::Duration,
+    events: &mut Events,
+) where
+    Proto: DecoderBuilder<Mono>,
+    Proto::Cmd: AddressCommand,
+    Mono: InfraMonotonic,
+{
+    let index = events.index;
+    events.index += 1;
+
+    let id = <<Proto as Protocol>::Cmd as AddressCommand>::ID;
+
+    if locked.is_some_and(|locked| locked != id) {
+        return;
+    }
+
+    let start = events.starts[index].take();
+
+    match ProtocolDecoder::<Proto, Mono>::event(decoder, edge, dt) {
+        State::Done => {
+            let cmd = ProtocolDecoder::<Proto, Mono>::command(decoder);
+            ProtocolDecoder::<Proto, Mono>::reset(decoder);
+
+            if let Some(cmd) = cmd {
+                if events.pending.as_ref().is_none_or(|p| index < p.index) {
+                    events.pending = Some(Pending {
+                        index,
+                        start: start.unwrap_or(events.edge),
+                        hold: HOLD,
+                        cmd: cmd.into(),
+                    });
+                }
+            }
+        }
+        State::Error(err) => {
+            ProtocolDecoder::<Proto, Mono>::reset(decoder);
+            events.error = events.error.or(Some(err));
+        }
+        State::Receiving => {
+            events.starts[index] = Some(start.unwrap_or(events.edge));
+        }
+        State::Idle => (),
+    }
+}
+
+/// Notify the decoder of `Proto` of the timeout, unless another protocol is locked
+#[allow(dead_code)]
+fn decoder_timeout<Proto, Mono, const HOLD: bool>(
+    decoder: &mut Proto::Decoder,
+    locked: Option<ProtocolId>,
+    dt: Mono::Duration,
+    events: &mut Events,
+) where
+    Proto: DecoderBuilder<Mono>,
+    Proto::Cmd: AddressCommand,
+    Mono: InfraMonotonic,
+{
+    let index = events.index;
+    events.index += 1;
+
+    let id = <<Proto as Protocol>::Cmd as AddressCommand>::ID;
+
+    if locked.is_some_and(|locked| locked != id)
+        || !ProtocolDecoder::<Proto, Mono>::timeout(decoder, dt)
+    {
+        return;
+    }
+
+    let start = events.starts[index].take();
+    let cmd = ProtocolDecoder::<Proto, Mono>::command(decoder);
+    ProtocolDecoder::<Proto, Mono>::reset(decoder);
+
+    if let Some(cmd) = cmd {
+        if events.pending.as_ref().is_none_or(|p| index < p.index) {
+            events.pending = Some(Pending {
+                index,
+                start: start.unwrap_or(events.edge),
+                hold: HOLD,
+                cmd: cmd.into(),
+            });
+        }
+    }
+}
+
+macro_rules! decoders {
+    ($($feature:literal $field:ident: $proto:ty => $hold:literal,)*) => {
+        /// The decoders of all compiled-in protocols, in detection order
+        struct Decoders<Mono: InfraMonotonic> {
+            $(
+                #[cfg(feature = $feature)]
+                $field: <$proto as DecoderBuilder<Mono>>::Decoder,
+            )*
+            mono: PhantomData<Mono>,
+        }
+
+        impl<Mono: InfraMonotonic> Decoders<Mono> {
+            #[allow(unused_variables)]
+            fn new(freq: u32) -> Self {
+                Decoders {
+                    $(
+                        #[cfg(feature = $feature)]
+                        $field: <$proto as DecoderBuilder<Mono>>::build(freq),
+                    )*
+                    mono: PhantomData,
+                }
+            }
+
+            fn reset(&mut self) {
+                $(
+                    #[cfg(feature = $feature)]
+                    ProtocolDecoder::<$proto, Mono>::reset(&mut self.$field);
+                )*
+            }
+
+            #[allow(unused_variables)]
+            fn event(
+                &mut self,
+                locked: Option<ProtocolId>,
+                edge: bool,
+                dt: Mono::Duration,
+                events: &mut Events,
+            ) {
+                $(
+                    #[cfg(feature = $feature)]
+                    decoder_event::<$proto, Mono, $hold>(&mut self.$field, locked, edge, dt, events);
+                )*
+            }
+
+            #[allow(unused_variables)]
+            fn timeout(
+                &mut self,
+                locked: Option<ProtocolId>,
+                dt: Mono::Duration,
+                events: &mut Events,
+            ) {
+                $(
+                    #[cfg(feature = $feature)]
+                    decoder_timeout::<$proto, Mono, $hold>(&mut self.$field, locked, dt, events);
+                )*
+            }
+        }
+    };
+}
+
+// Protocols marked `true` have frames that can be the start of frames of protocols listed before
+decoders! {
+    "nec" nec: crate::protocol::Nec => false,
+    "nec" apple_nec: crate::protocol::AppleNec => false,
+    "nec" nec16: crate::protocol::Nec16 => false,
+    "nec" samsung_nec: crate::protocol::SamsungNec => false,
+    "jvc" jvc: crate::protocol::Jvc => true,
+    "denon" denon: crate::protocol::Denon => false,
+    "kaseikyo" kaseikyo: crate::protocol::Kaseikyo => false,
+    "sharp" sharp: crate::protocol::Sharp => false,
+    "sbp" sbp: crate::protocol::Sbp => false,
+    "sirc" sirc: crate::protocol::Sirc => false,
+    "rc5" rc5: crate::protocol::Rc5 => false,
+    "rc6" rc6: crate::protocol::Rc6 => false,
+    "nrc17" nrc17: crate::protocol::Nrc17 => false,
+    "grundig" grundig: crate::protocol::Grundig => true,
+    "rcmm" rcmm: crate::protocol::RcMm => false,
+    "xmp" xmp: crate::protocol::Xmp => false,
+    "lego_pf" lego_pf: crate::protocol::LegoPf => false,
+}
@@ -0,0 +1,171 @@
+use std::vec::Vec;
+
+use crate::{
+    cmd::{AddressCommand, AnyCommand},
+    protocol::{
+        denon::DenonCommand,
+        jvc::JvcCommand,
+        kaseikyo::{KaseikyoCommand, Vendor},
+        lego_pf::LegoPfCommand,
+        nec::{AppleNecCommand, Nec16Command, NecCommand, SamsungNecCommand},
+        nrc17::Nrc17Command,
+        rc5::Rc5Command,
+        rc6::Rc6Command,
+        rcmm::RcMmCommand,
+        sbp::SbpCommand,
+        sharp::SharpCommand,
+        sirc::SircCommand,
+        xmp::XmpCommand,
+        AppleNec, Denon, Grundig, Jvc, Kaseikyo, LegoPf, Nec, Nec16, Nrc17, Rc5, Rc6, RcMm,
+        SamsungNec, Sbp, Sharp, Sirc, Xmp,
+    },
+    receiver::{AutoReceiver, DecodingError},
+    sender::{ProtocolEncoder, PulsedataBuffer},
+    ProtocolId,
+};
+
+const FREQ: u32 = 1_000_000;
+
+/// Feed the frame and the rising edge of the next one
+fn feed(recv: &mut AutoReceiver, buf: &[u32]) -> Vec<AnyCommand> {
+    // A buffer ending with a space ends with a rising edge, add a mark
+    let next: &[u32] = if buf.len() & 0x1 == 0 {
+        &[100_000]
+    } else {
+        &[560, 100_000]
+    };
+
+    buf.iter()
+        .chain(next)
+        .enumerate()
+        .filter_map(|(i, dt)| recv.event(*dt, i % 2 == 0).ok().flatten())
+        .collect()
+}
+
+fn detect<P>(cmd: P::Cmd)
+where
+    P: ProtocolEncoder<FREQ>,
+    P::Cmd: AddressCommand,
+{
+    let mut ptb = PulsedataBuffer::<256>::new();
+    ptb.load::<P, FREQ>(&cmd);
+
+    let mut recv: AutoReceiver = AutoReceiver::new(FREQ);
+    let cmds = feed(&mut recv, ptb.buffer());
+
+    let id = <P::Cmd as AddressCommand>::ID;
+    assert_eq!(recv.protocol(), Some(id));
+
+    let first = &cmds[0];
+    assert_eq!(first.protocol, id);
+    assert_eq!(first.address, cmd.address());
+    assert_eq!(first.command, cmd.command());
+}
+
+#[test]
+fn detect_protocols() {
+    detect::<Nec>(NecCommand {
+        addr: 0x12,
+        cmd: 0x34,
+        repeat: false,
+    });
+    detect::<AppleNec>(AppleNecCommand::new(0x0E, 0x02, 0x5D));
+    detect::<Nec16>(Nec16Command {
+        addr: 0x1234,
+        cmd: 0x56,
+        repeat: false,
+    });
+    detect::<SamsungNec>(SamsungNecCommand {
+        addr: 0x07,
+        cmd: 0x02,
+        repeat: false,
+    });
+    detect::<Jvc>(JvcCommand::new(0x03, 0x17));
+    detect::<Kaseikyo>(KaseikyoCommand::new(Vendor::Panasonic, 0x8, 0x01, 0x3D));
+    detect::<Denon>(DenonCommand::new(0x02, 0x01, 0xE2));
+    detect::<Sharp>(SharpCommand::new(0x11, 0x5C));
+    detect::<Sbp>(SbpCommand::create(0x707, 0x02).unwrap());
+    detect::<Sirc>(SircCommand::new20(0x1A, 0x15, 0x44));
+    detect::<Rc5>(Rc5Command::new(20, 12, false));
+    detect::<Rc6>(Rc6Command::new(0, 12));
+    detect::<Nrc17>(Nrc17Command::new(0x04, 0x02, 0x11));
+    detect::<Grundig>(crate::protocol::grundig::GrundigCommand::new(0x0B4));
+    detect::<RcMm>(RcMmCommand::new(1, 2, 0x55).unwrap());
+    detect::<Xmp>(XmpCommand::new(0x44, 0x15, 0x0E, 0x2F01));
+    detect::<LegoPf>(LegoPfCommand::single_output(1, false, 3));
+}
+
+#[test]
+fn locked_protocol() {
+    let mut ptb = PulsedataBuffer::<128>::new();
+    let mut recv: AutoReceiver = AutoReceiver::new(FREQ);
+
+    ptb.load::<Rc6, FREQ>(&Rc6Command::new(0, 12));
+    assert_eq!(feed(&mut recv, ptb.buffer()).len(), 1);
+    assert_eq!(recv.protocol(), Some(ProtocolId::Rc6));
+
+    // Only the Rc6 decoder gets the edges
+    ptb.reset();
+    ptb.load::<Nec, FREQ>(&NecCommand {
+        addr: 0x12,
+        cmd: 0x34,
+        repeat: false,
+    });
+    assert!(feed(&mut recv, ptb.buffer()).is_empty());
+
+    recv.unlock();
+    let cmds = feed(&mut recv, ptb.buffer());
+    assert_eq!(cmds[0].protocol, ProtocolId::Nec);
+    assert_eq!(recv.protocol(), Some(ProtocolId::Nec));
+
+    recv.lock(ProtocolId::Rc5);
+    assert!(feed(&mut recv, ptb.buffer()).is_empty());
+}
+
+#[test]
+fn report_errors() {
+    let mut ptb = PulsedataBuffer::<192>::new();
+    ptb.load::<LegoPf, FREQ>(&LegoPfCommand::single_output(0, false, 7));
+
+    let mut recv: AutoReceiver = AutoReceiver::new(FREQ);
+    recv.lock(ProtocolId::LegoPf);
+
+    // Flip the last bit of the first message, a zero
+    let mut buf = Vec::from(&ptb.buffer()[..36]);
+    buf[34] = 553;
+
+    let errors = buf
+        .iter()
+        .enumerate()
+        .filter_map(|(i, dt)| recv.event(*dt, i % 2 == 0).err())
+        .collect::<Vec<_>>();
+
+    assert_eq!(errors, [DecodingError::Validation]);
+}
+
+#[test]
+fn timeout() {
+    let mut ptb = PulsedataBuffer::<64>::new();
+    let cmd = SircCommand::new(0x01, 0x15);
+    ptb.load::<Sirc, FREQ>(&cmd);
+
+    // The burst ends with the last mark, no edge follows
+    let buf = ptb.buffer();
+    let buf = &buf[..buf.len() - 1];
+
+    let mut recv: AutoReceiver = AutoReceiver::new(FREQ);
+    let cmds = buf
+        .iter()
+        .enumerate()
+        .filter_map(|(i, dt)| recv.event(*dt, i % 2 == 0).ok().flatten())
+        .collect::<Vec<_>>();
+    assert!(cmds.is_empty());
+
+    assert!(recv.timeout(1_000).is_none());
+
+    let res = recv.timeout(30_000).unwrap();
+    assert_eq!(res.protocol, ProtocolId::Sirc);
+    assert_eq!(res.address, cmd.address());
+    assert_eq!(res.command, cmd.command());
+    assert_eq!(recv.protocol(), Some(ProtocolId::Sirc));
+}
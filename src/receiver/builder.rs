@@ -3,16 +3,38 @@ use core::marker::PhantomData;
 #[cfg(feature = "embedded-hal")]
 use embedded_hal::digital::v2::InputPin;
 
+#[cfg(feature = "ac")]
+use crate::protocol::ac::{Daikin, Gree, Mitsubishi};
 #[cfg(feature = "denon")]
 use crate::protocol::Denon;
+#[cfg(feature = "grundig")]
+use crate::protocol::Grundig;
+#[cfg(feature = "hash")]
+use crate::protocol::Hash;
+#[cfg(feature = "jvc")]
+use crate::protocol::Jvc;
+#[cfg(feature = "kaseikyo")]
+use crate::protocol::Kaseikyo;
+#[cfg(feature = "lego_pf")]
+use crate::protocol::LegoPf;
+#[cfg(feature = "nrc17")]
+use crate::protocol::Nrc17;
 #[cfg(feature = "rc5")]
 use crate::protocol::Rc5;
 #[cfg(feature = "rc6")]
 use crate::protocol::Rc6;
-#[cfg(feature = "sbp")]
-use crate::protocol::Sbp;
+#[cfg(feature = "rcmm")]
+use crate::protocol::RcMm;
+#[cfg(feature = "sharp")]
+use crate::protocol::Sharp;
+#[cfg(feature = "sirc")]
+use crate::protocol::Sirc;
+#[cfg(feature = "xmp")]
+use crate::protocol::Xmp;
 #[cfg(feature = "nec")]
-use crate::protocol::{nec::NecCommand, AppleNec, Nec, Nec16, SamsungNec};
+use crate::protocol::{nec::NecCommand, AppleNec, Nec, Nec16, Nec42, NecExt, SamsungNec};
+#[cfg(feature = "sbp")]
+use crate::protocol::{Samsung36, Sbp};
 #[cfg(feature = "remotes")]
 use crate::remotecontrol::{Button, RemoteControlModel};
 use crate::{
@@ -76,6 +98,16 @@ where
         self.protocol()
     }
 
+    #[cfg(feature = "nec")]
+    pub fn nec_ext(self) -> Builder<NecExt, Input, Mono, <NecExt as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "nec")]
+    pub fn nec42(self) -> Builder<Nec42, Input, Mono, <Nec42 as Protocol>::Cmd> {
+        self.protocol()
+    }
+
     #[cfg(feature = "nec")]
     pub fn nec_samsung(self) -> Builder<SamsungNec, Input, Mono, <SamsungNec as Protocol>::Cmd> {
         self.protocol()
@@ -101,11 +133,86 @@ where
         self.protocol()
     }
 
+    #[cfg(feature = "sbp")]
+    pub fn samsung36(self) -> Builder<Samsung36, Input, Mono, <Samsung36 as Protocol>::Cmd> {
+        self.protocol()
+    }
+
     #[cfg(feature = "denon")]
     pub fn denon(self) -> Builder<Denon, Input, Mono, <Denon as Protocol>::Cmd> {
         self.protocol()
     }
 
+    #[cfg(feature = "sirc")]
+    pub fn sirc(self) -> Builder<Sirc, Input, Mono, <Sirc as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "jvc")]
+    pub fn jvc(self) -> Builder<Jvc, Input, Mono, <Jvc as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "kaseikyo")]
+    pub fn kaseikyo(self) -> Builder<Kaseikyo, Input, Mono, <Kaseikyo as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "sharp")]
+    pub fn sharp(self) -> Builder<Sharp, Input, Mono, <Sharp as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "nrc17")]
+    pub fn nrc17(self) -> Builder<Nrc17, Input, Mono, <Nrc17 as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "grundig")]
+    pub fn grundig(self) -> Builder<Grundig, Input, Mono, <Grundig as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "rcmm")]
+    pub fn rcmm(self) -> Builder<RcMm, Input, Mono, <RcMm as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "xmp")]
+    pub fn xmp(self) -> Builder<Xmp, Input, Mono, <Xmp as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "lego_pf")]
+    pub fn lego_pf(self) -> Builder<LegoPf, Input, Mono, <LegoPf as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "hash")]
+    /// Hash of the frame, for remotes of unknown protocols
+    ///
+    /// The end of the frame is the pause after it. The `PeriodicPoll` receiver sees the pause,
+    /// other receivers report the frame on the first edge of the next frame, unless
+    /// `Receiver::timeout` is called after the last edge
+    pub fn hash(self) -> Builder<Hash, Input, Mono, <Hash as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "ac")]
+    pub fn mitsubishi_ac(self) -> Builder<Mitsubishi, Input, Mono, <Mitsubishi as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "ac")]
+    pub fn daikin_ac(self) -> Builder<Daikin, Input, Mono, <Daikin as Protocol>::Cmd> {
+        self.protocol()
+    }
+
+    #[cfg(feature = "ac")]
+    pub fn gree_ac(self) -> Builder<Gree, Input, Mono, <Gree as Protocol>::Cmd> {
+        self.protocol()
+    }
+
     #[cfg(feature = "remotes")]
     /// Use Remote control
     pub fn remotecontrol<Remote>(self, _: Remote) -> Builder<Proto, Input, Mono, Button<Remote>>
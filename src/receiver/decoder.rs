@@ -40,6 +40,16 @@ where
     /// Get the time spans
     fn spans(&self) -> &PulseSpans<Mono>;
 
+    /// Notify the state machine that there has been no edge for `dt` since the last event
+    ///
+    /// Some frames can only be told complete from the pause after them. Their decoders complete
+    /// the frame here, instead of on the first edge of the next frame. Returns true if a frame
+    /// was completed, `command` returns it
+    fn timeout(&mut self, dt: Mono::Duration) -> bool {
+        let _ = dt;
+        false
+    }
+
     /// I don't care about the details, just give me a command (or an error)!
     fn event_total(
         &mut self,
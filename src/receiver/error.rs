@@ -10,6 +10,8 @@ pub enum DecodingError {
     Validation,
     /// Remotecontrol decode error
     RemoteControlError,
+    /// Repeat frame without a preceding command in the repeat window
+    OrphanRepeat,
 }
 
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
@@ -19,6 +21,7 @@ pub enum Error<PinErr> {
     Data,
     Validation,
     RemoteControlError,
+    OrphanRepeat,
     Hal(PinErr),
 }
 
@@ -29,6 +32,7 @@ impl<PinErr> From<DecodingError> for Error<PinErr> {
             DecodingError::Data => Error::Data,
             DecodingError::Validation => Error::Validation,
             DecodingError::RemoteControlError => Error::RemoteControlError,
+            DecodingError::OrphanRepeat => Error::OrphanRepeat,
         }
     }
 }
@@ -0,0 +1,164 @@
+//! Key press, hold and release events
+//!
+//! Remotes send a command over and over while a button is held, and nothing when it's released.
+//! [`KeyEvents`] turns the commands of a receiver into [`KeyEvent`]s:
+//!
+//!  - Protocols with a toggle bit, as Rc5 and Rc6, flip it for every new press. A command with
+//!    the same toggle bit is the key being held
+//!  - Nec sends the command once, followed by repeat frames. A repeat frame is the key being held,
+//!    a full frame after repeat frames is a new press
+//!  - Other protocols send the full frame while the key is held
+//!
+//! A key is released when another key is pressed, or when no command has been received for the
+//! release timeout. The timeout has to be longer than the repeat period of the remote, and
+//! [`KeyEvents::poll`] has to be called to notice it.
+
+use crate::{cmd::AddressCommand, receiver::time::InfraMonotonic};
+
+#[cfg(all(test, feature = "nec", feature = "rc5", feature = "sirc"))]
+mod tests;
+
+/// Default release timeout, in us. Longer than the 108 ms repeat period of Nec and the 114 ms
+/// frame period of Rc5 and Rc6
+pub const RELEASE_TIMEOUT: u32 = 150_000;
+
+/// Key event
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum KeyEvent<Cmd, Dur> {
+    /// The key was pressed
+    Pressed(Cmd),
+    /// The key is still held, for the duration since it was pressed
+    Held(Cmd, Dur),
+    /// The key was released
+    Released(Cmd),
+}
+
+/// Press, hold and release tracking of the commands of a receiver
+pub struct KeyEvents<Cmd, Mono: InfraMonotonic = u32> {
+    /// Release the key after this long without a command
+    timeout: Mono::Duration,
+    /// The pressed key
+    key: Option<Key<Cmd, Mono>>,
+}
+
+/// Pressed key
+struct Key<Cmd, Mono: InfraMonotonic> {
+    /// Command of the press
+    cmd: Cmd,
+    /// Time of the press
+    pressed: Mono::Instant,
+    /// Time of the last command
+    last: Mono::Instant,
+    /// Repeat frames have been received
+    repeats: bool,
+}
+
+impl<Cmd, Mono> KeyEvents<Cmd, Mono>
+where
+    Cmd: AddressCommand + Clone,
+    Mono: InfraMonotonic,
+{
+    /// Key events with a clock of `freq` Hz and the default release timeout
+    pub fn new(freq: u32) -> Self {
+        Self::with_release_timeout(freq, RELEASE_TIMEOUT)
+    }
+
+    /// Key events with a clock of `freq` Hz, releasing the key after `timeout` us without a
+    /// command
+    pub fn with_release_timeout(freq: u32, timeout: u32) -> Self {
+        KeyEvents {
+            timeout: Mono::create_span(freq, timeout, 0).low(),
+            key: None,
+        }
+    }
+
+    /// Command of the pressed key
+    pub fn pressed(&self) -> Option<&Cmd> {
+        self.key.as_ref().map(|key| &key.cmd)
+    }
+
+    /// Feed a command received at `t`. A press of a new key releases the pressed key first
+    pub fn command(
+        &mut self,
+        cmd: Cmd,
+        t: Mono::Instant,
+    ) -> impl Iterator<Item = KeyEvent<Cmd, Mono::Duration>> {
+        let mut released = None;
+
+        if let Some(key) = &mut self.key {
+            if !expired::<Mono>(self.timeout, key.last, t) && key.continued_by(&cmd) {
+                key.last = t;
+                key.repeats |= cmd.is_repeat();
+
+                let held = Mono::checked_sub(t, key.pressed).unwrap_or(Mono::ZERO_DURATION);
+                return [Some(KeyEvent::Held(key.cmd.clone(), held)), None]
+                    .into_iter()
+                    .flatten();
+            }
+
+            released = self.key.take().map(|key| KeyEvent::Released(key.cmd));
+        }
+
+        // A repeat without a pressed key
+        if cmd.is_repeat() {
+            return [released, None].into_iter().flatten();
+        }
+
+        self.key = Some(Key {
+            cmd: cmd.clone(),
+            pressed: t,
+            last: t,
+            repeats: false,
+        });
+
+        [released, Some(KeyEvent::Pressed(cmd))]
+            .into_iter()
+            .flatten()
+    }
+
+    /// Release the pressed key if the release timeout has passed at `t`
+    pub fn poll(&mut self, t: Mono::Instant) -> Option<KeyEvent<Cmd, Mono::Duration>> {
+        let key = self.key.as_ref()?;
+
+        if !expired::<Mono>(self.timeout, key.last, t) {
+            return None;
+        }
+
+        self.key.take().map(|key| KeyEvent::Released(key.cmd))
+    }
+
+    /// Release the pressed key
+    pub fn release(&mut self) -> Option<KeyEvent<Cmd, Mono::Duration>> {
+        self.key.take().map(|key| KeyEvent::Released(key.cmd))
+    }
+}
+
+impl<Cmd: AddressCommand, Mono: InfraMonotonic> Key<Cmd, Mono> {
+    /// `cmd` is the key being held
+    fn continued_by(&self, cmd: &Cmd) -> bool {
+        if cmd.address() != self.cmd.address() || cmd.command() != self.cmd.command() {
+            return false;
+        }
+
+        if cmd.is_repeat() {
+            return true;
+        }
+
+        match (self.cmd.toggle(), cmd.toggle()) {
+            (Some(pressed), Some(toggle)) => pressed == toggle,
+            // A full frame after the repeat frames is a new press
+            _ => !self.repeats,
+        }
+    }
+}
+
+/// The release timeout has passed at `t` since the command at `last`
+fn expired<Mono: InfraMonotonic>(
+    timeout: Mono::Duration,
+    last: Mono::Instant,
+    t: Mono::Instant,
+) -> bool {
+    // A clock going backwards or wrapping around
+    Mono::checked_sub(t, last).is_none_or(|dt| dt > timeout)
+}
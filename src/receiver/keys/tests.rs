@@ -0,0 +1,135 @@
+use std::vec::Vec;
+
+use crate::{
+    protocol::{nec::NecCommand, rc5::Rc5Command, sirc::SircCommand},
+    receiver::{KeyEvent, KeyEvents},
+};
+
+const FREQ: u32 = 1_000_000;
+
+const fn nec(cmd: u8, repeat: bool) -> NecCommand {
+    NecCommand {
+        addr: 0x12,
+        cmd,
+        repeat,
+    }
+}
+
+fn events<Cmd: crate::cmd::AddressCommand + Clone>(
+    keys: &mut KeyEvents<Cmd>,
+    cmd: Cmd,
+    t: u32,
+) -> Vec<KeyEvent<Cmd, u32>> {
+    keys.command(cmd, t).collect()
+}
+
+#[test]
+fn nec_repeats() {
+    let mut keys = KeyEvents::new(FREQ);
+    let play = nec(0x34, false);
+
+    assert_eq!(events(&mut keys, play, 0), [KeyEvent::Pressed(play)]);
+    assert_eq!(
+        events(&mut keys, nec(0x34, true), 40_000),
+        [KeyEvent::Held(play, 40_000)]
+    );
+    assert_eq!(
+        events(&mut keys, nec(0x34, true), 148_000),
+        [KeyEvent::Held(play, 148_000)]
+    );
+    assert_eq!(keys.pressed(), Some(&play));
+
+    assert_eq!(keys.poll(200_000), None);
+    assert_eq!(keys.poll(300_000), Some(KeyEvent::Released(play)));
+    assert_eq!(keys.pressed(), None);
+    assert_eq!(keys.poll(400_000), None);
+}
+
+#[test]
+fn nec_new_press() {
+    let mut keys = KeyEvents::new(FREQ);
+    let play = nec(0x34, false);
+
+    events(&mut keys, play, 0);
+    events(&mut keys, nec(0x34, true), 40_000);
+
+    // A full frame after the repeats is the key pressed again
+    assert_eq!(
+        events(&mut keys, play, 120_000),
+        [KeyEvent::Released(play), KeyEvent::Pressed(play)]
+    );
+
+    // Another key
+    let stop = nec(0x35, false);
+    assert_eq!(
+        events(&mut keys, stop, 200_000),
+        [KeyEvent::Released(play), KeyEvent::Pressed(stop)]
+    );
+
+    // Repeat of another key, the first frame was lost
+    assert_eq!(
+        events(&mut keys, nec(0x36, true), 250_000),
+        [KeyEvent::Released(stop)]
+    );
+    // Repeat after the timeout
+    assert!(events(&mut keys, nec(0x36, true), 500_000).is_empty());
+}
+
+#[test]
+fn rc5_toggle() {
+    let mut keys = KeyEvents::new(FREQ);
+    let first = Rc5Command::new(20, 9, false);
+    let second = Rc5Command::new(20, 9, true);
+
+    assert_eq!(events(&mut keys, first, 0), [KeyEvent::Pressed(first)]);
+    assert_eq!(
+        events(&mut keys, first, 114_000),
+        [KeyEvent::Held(first, 114_000)]
+    );
+    // Pressed again, with the toggle bit flipped
+    assert_eq!(
+        events(&mut keys, second, 228_000),
+        [KeyEvent::Released(first), KeyEvent::Pressed(second)]
+    );
+    assert_eq!(
+        events(&mut keys, second, 342_000),
+        [KeyEvent::Held(second, 114_000)]
+    );
+}
+
+#[test]
+fn sirc_frames() {
+    let mut keys = KeyEvents::new(FREQ);
+    let cmd = SircCommand::new(1, 21);
+
+    assert_eq!(events(&mut keys, cmd, 0), [KeyEvent::Pressed(cmd)]);
+    assert_eq!(
+        events(&mut keys, cmd, 45_000),
+        [KeyEvent::Held(cmd, 45_000)]
+    );
+    assert_eq!(
+        events(&mut keys, cmd, 90_000),
+        [KeyEvent::Held(cmd, 90_000)]
+    );
+
+    // The same key after the timeout is a new press
+    assert_eq!(
+        events(&mut keys, cmd, 300_000),
+        [KeyEvent::Released(cmd), KeyEvent::Pressed(cmd)]
+    );
+    assert_eq!(keys.release(), Some(KeyEvent::Released(cmd)));
+}
+
+#[test]
+fn release_timeout() {
+    let mut keys = KeyEvents::with_release_timeout(FREQ, 50_000);
+    let cmd = SircCommand::new(1, 21);
+
+    events(&mut keys, cmd, 0);
+    assert_eq!(
+        events(&mut keys, cmd, 45_000),
+        [KeyEvent::Held(cmd, 45_000)]
+    );
+    assert_eq!(keys.poll(95_000), None);
+    assert_eq!(keys.poll(96_000), Some(KeyEvent::Released(cmd)));
+}
@@ -48,6 +48,17 @@ where
         self.clock = self.clock.wrapping_add(1);
 
         if edge == self.edge {
+            // Complete the frames that end with the pause after them
+            if !edge
+                && self
+                    .decoder
+                    .timeout(self.clock.wrapping_sub(self.last_edge))
+            {
+                let cmd = self.decoder.command().map(Into::into);
+                self.decoder.reset();
+                return Ok(cmd);
+            }
+
             return Ok(None);
         }
 
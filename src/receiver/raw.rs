@@ -0,0 +1,288 @@
+//! Raw pulse capture
+//!
+//! Records the mark and space lengths of a pulse train, independent of the protocol, for
+//! learning remotes. The capture starts with the first mark and ends with a space of at least
+//! [`IDLE`] us. Spaces of at least [`FRAME_GAP`] us split the capture into the frame and the
+//! repeat frames.
+//!
+//! A capture can be sent again with `Sender::load_raw`.
+
+#[cfg(feature = "embedded-hal")]
+use embedded_hal::digital::v2::InputPin;
+
+#[cfg(feature = "embedded-hal")]
+use crate::receiver::Error;
+use crate::{
+    protocol::utils::scale_with_samplerate,
+    receiver::{time::InfraMonotonic, NoPin},
+};
+
+#[cfg(all(test, feature = "nec"))]
+mod tests;
+
+/// Spaces this long end the capture, in us
+pub const IDLE: u32 = 150_000;
+/// Spaces this long end a frame, in us
+pub const FRAME_GAP: u32 = 20_000;
+
+/// Receiver recording the raw pulse lengths into a buffer
+pub struct RawCapture<'a, Input = NoPin, Mono: InfraMonotonic = u32> {
+    freq: u32,
+    /// Mark and space lengths in us, starting with a mark
+    buf: &'a mut [u32],
+    len: usize,
+    /// Pulses didn't fit into the buffer
+    truncated: bool,
+    /// A mark was started
+    recording: bool,
+    /// The capture in the buffer was returned
+    complete: bool,
+    input: Input,
+    prev_instant: Mono::Instant,
+    /// Clock of the polled interface
+    clock: u32,
+    /// Last seen edge
+    edge: bool,
+    /// Seen at
+    last_edge: u32,
+    /// [`IDLE`] in clock ticks of the polled interface
+    idle_ticks: u32,
+}
+
+/// Captured pulse train
+#[derive(Debug)]
+pub struct RawFrame<'a> {
+    pulses: &'a [u32],
+    truncated: bool,
+}
+
+impl<'a> RawFrame<'a> {
+    /// Mark and space lengths in us, starting with a mark and ending with the last mark
+    pub fn pulses(&self) -> &'a [u32] {
+        self.pulses
+    }
+
+    /// The pulses didn't fit into the buffer, the end of the capture is missing
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// The frames of the capture, without the gaps
+    pub fn frames(&self) -> impl Iterator<Item = &'a [u32]> {
+        let pulses = self.pulses;
+        let mut start = 0;
+
+        core::iter::from_fn(move || {
+            if start >= pulses.len() {
+                return None;
+            }
+
+            // Spaces are at odd indices
+            let end = (start + 1..pulses.len())
+                .step_by(2)
+                .find(|&i| pulses[i] >= FRAME_GAP)
+                .unwrap_or(pulses.len());
+
+            let frame = &pulses[start..end];
+            start = end + 1;
+            Some(frame)
+        })
+    }
+
+    /// The first frame
+    pub fn frame(&self) -> &'a [u32] {
+        self.frames().next().unwrap_or_default()
+    }
+
+    /// The frames after the first one
+    pub fn repeats(&self) -> impl Iterator<Item = &'a [u32]> {
+        self.frames().skip(1)
+    }
+
+    /// Time from the start of the first frame to the start of the second, in us
+    pub fn repeat_period(&self) -> Option<u32> {
+        let mut frames = self.frames();
+        let first = frames.next()?;
+        frames.next()?;
+
+        let gap = self.pulses[first.len()];
+        Some(first.iter().sum::<u32>() + gap)
+    }
+}
+
+impl<'a, Mono: InfraMonotonic> RawCapture<'a, NoPin, Mono> {
+    /// Capture into `buf` with a clock of `freq` Hz
+    pub fn new(freq: u32, buf: &'a mut [u32]) -> Self {
+        Self::with_input(freq, NoPin, buf)
+    }
+
+    pub fn event(&mut self, dt: Mono::Duration, edge: bool) -> Option<RawFrame<'_>> {
+        self.event_edge(dt, edge)
+    }
+
+    pub fn event_instant(&mut self, t: Mono::Instant, edge: bool) -> Option<RawFrame<'_>> {
+        let dt = Mono::checked_sub(t, self.prev_instant).unwrap_or(Mono::ZERO_DURATION);
+        self.prev_instant = t;
+
+        self.event_edge(dt, edge)
+    }
+}
+
+impl<'a, Input> RawCapture<'a, Input, u32> {
+    /// Sample the input, call at the frequency of the capture
+    pub fn poll_base(&mut self, edge: bool) -> Option<RawFrame<'_>> {
+        self.clock = self.clock.wrapping_add(1);
+
+        if edge == self.edge {
+            let idle = self.clock.wrapping_sub(self.last_edge);
+
+            // No edge after the last mark
+            if !edge && self.recording && self.len > 0 && idle >= self.idle_ticks {
+                self.recording = false;
+                return Some(self.complete());
+            }
+            return None;
+        }
+
+        let ds = self.clock.wrapping_sub(self.last_edge);
+        self.edge = edge;
+        self.last_edge = self.clock;
+
+        self.event_edge(ds, edge)
+    }
+}
+
+impl<'a> RawCapture<'a, NoPin, u32> {
+    pub fn poll(&mut self, edge: bool) -> Option<RawFrame<'_>> {
+        self.poll_base(edge)
+    }
+}
+
+impl<'a, Input, Mono: InfraMonotonic> RawCapture<'a, Input, Mono> {
+    pub fn with_input(freq: u32, input: Input, buf: &'a mut [u32]) -> Self {
+        RawCapture {
+            freq,
+            buf,
+            len: 0,
+            truncated: false,
+            recording: false,
+            complete: false,
+            input,
+            prev_instant: Mono::ZERO_INSTANT,
+            clock: 0,
+            edge: false,
+            last_edge: 0,
+            idle_ticks: scale_with_samplerate(IDLE, freq),
+        }
+    }
+
+    /// Record an edge. Returns the capture on the first edge after the idle gap
+    pub fn event_edge(&mut self, dt: Mono::Duration, edge: bool) -> Option<RawFrame<'_>> {
+        if self.complete {
+            self.len = 0;
+            self.truncated = false;
+            self.complete = false;
+        }
+
+        if !self.recording {
+            // Wait for the first mark
+            self.recording = edge;
+            return None;
+        }
+
+        let us = Mono::to_micros(self.freq, dt);
+
+        if edge && us >= IDLE {
+            // This edge starts the next capture
+            return Some(self.complete());
+        }
+
+        if self.len < self.buf.len() {
+            self.buf[self.len] = us;
+            self.len += 1;
+        } else {
+            self.truncated = true;
+        }
+
+        None
+    }
+
+    /// Notify the capture that there has been no edge for `dt`. Completes the capture once
+    /// [`IDLE`] us have passed after the last mark, call it from a timer after the last edge
+    pub fn timeout(&mut self, dt: Mono::Duration) -> Option<RawFrame<'_>> {
+        if !self.recording || self.len == 0 || Mono::to_micros(self.freq, dt) < IDLE {
+            return None;
+        }
+
+        self.recording = false;
+        Some(self.complete())
+    }
+
+    /// Notify the capture that there has been no edge until `t`, see [`RawCapture::timeout`]
+    pub fn timeout_instant(&mut self, t: Mono::Instant) -> Option<RawFrame<'_>> {
+        let dt = Mono::checked_sub(t, self.prev_instant).unwrap_or(Mono::ZERO_DURATION);
+        self.timeout(dt)
+    }
+
+    /// Start over, dropping the pulses recorded so far
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.truncated = false;
+        self.recording = false;
+        self.complete = false;
+    }
+
+    fn complete(&mut self) -> RawFrame<'_> {
+        self.complete = true;
+
+        // Drop the space of a truncated capture, the pulses end with a mark
+        let len = if self.len & 0x1 == 0 {
+            self.len.saturating_sub(1)
+        } else {
+            self.len
+        };
+
+        RawFrame {
+            pulses: &self.buf[..len],
+            truncated: self.truncated,
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<'a, Pin: InputPin, Mono: InfraMonotonic> RawCapture<'a, Pin, Mono> {
+    /// Create a `RawCapture` with `pin` as input
+    pub fn with_pin(freq: u32, pin: Pin, buf: &'a mut [u32]) -> Self {
+        Self::with_input(freq, pin, buf)
+    }
+
+    pub fn event(&mut self, dt: Mono::Duration) -> Result<Option<RawFrame<'_>>, Error<Pin::Error>> {
+        let edge = self.input.is_low().map_err(Error::Hal)?;
+        Ok(self.event_edge(dt, edge))
+    }
+
+    pub fn event_instant(
+        &mut self,
+        t: Mono::Instant,
+    ) -> Result<Option<RawFrame<'_>>, Error<Pin::Error>> {
+        let edge = self.input.is_low().map_err(Error::Hal)?;
+
+        let dt = Mono::checked_sub(t, self.prev_instant).unwrap_or(Mono::ZERO_DURATION);
+        self.prev_instant = t;
+
+        Ok(self.event_edge(dt, edge))
+    }
+
+    /// Drop the capture and release the pin
+    pub fn release(self) -> Pin {
+        self.input
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<'a, Pin: InputPin> RawCapture<'a, Pin, u32> {
+    pub fn poll(&mut self) -> Result<Option<RawFrame<'_>>, Error<Pin::Error>> {
+        let edge = self.input.is_low().map_err(Error::Hal)?;
+        Ok(self.poll_base(edge))
+    }
+}
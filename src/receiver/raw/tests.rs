@@ -0,0 +1,142 @@
+use std::vec::Vec;
+
+use crate::{
+    protocol::{nec::NecCommand, Nec},
+    receiver::{BufferInputReceiver, RawCapture},
+    sender::{PulsedataBuffer, PulsedataSender},
+};
+
+const CMD: NecCommand = NecCommand {
+    addr: 0x12,
+    cmd: 0x34,
+    repeat: false,
+};
+const REPEAT: [u32; 3] = [9000, 2250, 560];
+
+/// Nec frame, two repeat frames and the idle gap, at 1 MHz
+fn nec_with_repeats() -> Vec<u32> {
+    let mut ptb = PulsedataBuffer::<128>::new();
+    ptb.load::<Nec, 1_000_000>(&CMD);
+
+    let mut pulses = Vec::from(ptb.buffer());
+    pulses.push(40_000);
+    pulses.extend_from_slice(&REPEAT);
+    pulses.push(96_000);
+    pulses.extend_from_slice(&REPEAT);
+    pulses.push(200_000);
+    pulses
+}
+
+#[test]
+fn capture_repeats() {
+    let pulses = nec_with_repeats();
+    let mut buf = [0; 128];
+    let mut capture: RawCapture = RawCapture::new(1_000_000, &mut buf);
+    let mut captured = 0;
+
+    for (i, dt) in pulses.iter().enumerate() {
+        if let Some(raw) = capture.event(*dt, i & 0x1 == 0) {
+            assert!(!raw.truncated());
+            // Without the leading zero
+            assert_eq!(raw.frame(), &pulses[1..68]);
+            assert_eq!(raw.repeats().collect::<Vec<_>>(), [REPEAT, REPEAT]);
+            assert_eq!(
+                raw.repeat_period(),
+                Some(pulses[1..68].iter().sum::<u32>() + 40_000)
+            );
+            assert_eq!(raw.pulses().len(), 67 + 2 * 4);
+            captured += 1;
+        }
+    }
+
+    assert_eq!(captured, 1);
+}
+
+#[test]
+fn capture_truncated() {
+    let pulses = nec_with_repeats();
+    let mut buf = [0; 10];
+    let mut capture: RawCapture = RawCapture::new(1_000_000, &mut buf);
+
+    let mut captured = false;
+    for (i, dt) in pulses.iter().enumerate() {
+        if let Some(raw) = capture.event(*dt, i & 0x1 == 0) {
+            assert!(raw.truncated());
+            assert_eq!(raw.pulses(), &pulses[1..10]);
+            assert_eq!(raw.repeats().count(), 0);
+            captured = true;
+        }
+    }
+    assert!(captured);
+}
+
+#[test]
+fn polled_capture() {
+    const FREQ: u32 = 20_000;
+    let mut ptb = PulsedataBuffer::<128>::new();
+    ptb.load::<Nec, FREQ>(&CMD);
+
+    let mut buf = [0; 128];
+    let mut capture = RawCapture::new(FREQ, &mut buf);
+    let mut pinstate = false;
+    let mut frame = None;
+
+    // Ticks of the pulses followed by the idle gap
+    for n in ptb.buffer().iter().chain(&[4 * FREQ]) {
+        for _ in 0..*n {
+            if let Some(raw) = capture.poll(pinstate) {
+                frame = Some(Vec::from(raw.frame()));
+            }
+        }
+        pinstate = !pinstate;
+    }
+
+    let frame = frame.unwrap();
+    assert_eq!(frame.len(), 67);
+    // The header in 50 us ticks
+    assert_eq!(&frame[..2], &[9000, 4500]);
+}
+
+#[test]
+fn replay() {
+    let pulses = nec_with_repeats();
+    let mut buf = [0; 128];
+    let mut capture: RawCapture = RawCapture::new(1_000_000, &mut buf);
+    let mut sender = PulsedataSender::<128>::new();
+
+    for (i, dt) in pulses.iter().enumerate() {
+        if let Some(raw) = capture.event(*dt, i & 0x1 == 0) {
+            sender.load_raw::<40_000>(raw.frame());
+        }
+    }
+
+    let mut brecv = BufferInputReceiver::<Nec>::with_frequenzy(40_000);
+    assert_eq!(brecv.iter(sender.buffer()).next(), Some(CMD));
+}
+
+#[test]
+fn timeout() {
+    let pulses = nec_with_repeats();
+    // No edge after the last mark
+    let pulses = &pulses[..pulses.len() - 1];
+    let mut buf = [0; 128];
+    let mut capture: RawCapture = RawCapture::new(1_000_000, &mut buf);
+
+    for (i, dt) in pulses.iter().enumerate() {
+        assert!(capture.event(*dt, i & 0x1 == 0).is_none());
+    }
+
+    assert!(capture.timeout(100_000).is_none());
+
+    let raw = capture.timeout(150_000).unwrap();
+    assert_eq!(raw.frame(), &pulses[1..68]);
+    assert_eq!(raw.repeats().count(), 2);
+
+    assert!(capture.timeout(300_000).is_none());
+
+    // The next capture starts with the next mark
+    for (i, dt) in pulses.iter().enumerate() {
+        assert!(capture.event(*dt, i & 0x1 == 0).is_none());
+    }
+    assert_eq!(capture.timeout(150_000).unwrap().frame(), &pulses[1..68]);
+}
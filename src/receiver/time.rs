@@ -2,6 +2,7 @@ use core::ops::{Add, Sub};
 
 #[cfg(feature = "fugit")]
 mod fgt;
+pub mod fingerprint;
 mod primitives;
 
 pub trait InfraMonotonic: Sized {
@@ -21,6 +22,38 @@ pub trait InfraMonotonic: Sized {
     fn checked_sub(a: Self::Instant, b: Self::Instant) -> Option<Self::Duration>;
 
     fn create_span(freq: u32, p: u32, t: u32) -> Span<Self::Duration>;
+
+    /// Length of `dt` in us, saturated at `u32::MAX`
+    ///
+    /// The default searches the span lengths from `create_span` and rounds up, implementations
+    /// can convert directly
+    fn to_micros(freq: u32, dt: Self::Duration) -> u32 {
+        let shorter = |us| Self::create_span(freq, us, 0).low() < dt;
+
+        if !shorter(0) {
+            return 0;
+        }
+
+        // Double the upper bound, then halve the range to the shortest length not shorter than dt
+        let mut high = 1;
+        while shorter(high) {
+            if high > u32::MAX / 2 {
+                return u32::MAX;
+            }
+            high *= 2;
+        }
+
+        let mut low = high / 2;
+        while low + 1 < high {
+            let mid = low + (high - low) / 2;
+            if shorter(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        high
+    }
 }
 
 #[derive(Debug)]
@@ -29,27 +62,19 @@ pub struct Span<Dur> {
     high: Dur,
 }
 
+/// Spans of the pulse lengths of a protocol. Most protocols need at most 8
 #[derive(Debug)]
-pub struct PulseSpans<Mono: InfraMonotonic> {
-    pub(crate) spans: [Span<Mono::Duration>; 8],
+pub struct PulseSpans<Mono: InfraMonotonic, const N: usize = 8> {
+    pub(crate) spans: [Span<Mono::Duration>; N],
 }
 
-impl<Mono> PulseSpans<Mono>
+impl<Mono, const N: usize> PulseSpans<Mono, N>
 where
     Mono: InfraMonotonic,
 {
-    pub fn new(freq: u32, pulse: &[u32; 8], tolerance: &[u32; 8]) -> Self {
+    pub fn new(freq: u32, pulse: &[u32; N], tolerance: &[u32; N]) -> Self {
         PulseSpans {
-            spans: [
-                Mono::create_span(freq, pulse[0], tolerance[0]),
-                Mono::create_span(freq, pulse[1], tolerance[1]),
-                Mono::create_span(freq, pulse[2], tolerance[2]),
-                Mono::create_span(freq, pulse[3], tolerance[3]),
-                Mono::create_span(freq, pulse[4], tolerance[4]),
-                Mono::create_span(freq, pulse[5], tolerance[5]),
-                Mono::create_span(freq, pulse[6], tolerance[6]),
-                Mono::create_span(freq, pulse[7], tolerance[7]),
-            ],
+            spans: core::array::from_fn(|i| Mono::create_span(freq, pulse[i], tolerance[i])),
         }
     }
 
@@ -60,6 +85,11 @@ where
             .map(Into::into)
     }
 
+    /// Check if `pl` is within the span at `index`
+    pub fn matches(&self, index: usize, pl: Mono::Duration) -> bool {
+        self.spans.get(index).is_some_and(|span| span.contains(pl))
+    }
+
     pub fn check_overlaps(&self) -> bool {
         for i in 0..self.spans.len() {
             for j in 0..self.spans.len() {
@@ -81,6 +111,11 @@ impl<Dur> Span<Dur>
 where
     Dur: PartialOrd + Copy,
 {
+    /// Shortest duration in the span
+    pub(crate) fn low(&self) -> Dur {
+        self.low
+    }
+
     fn contains(&self, other: Dur) -> bool {
         self.low <= other && other <= self.high
     }
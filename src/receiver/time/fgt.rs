@@ -15,6 +15,10 @@ impl<const NOM: u32, const DENOM: u32> InfraMonotonic for Instant<u32, NOM, DENO
     fn create_span(_freq: u32, p: u32, t: u32) -> Span<Self::Duration> {
         Span::<Self::Duration>::new(p.micros(), t)
     }
+
+    fn to_micros(_freq: u32, dt: Self::Duration) -> u32 {
+        dt.to_micros()
+    }
 }
 
 impl<const NOM: u32, const DENOM: u32> Span<Duration<u32, NOM, DENOM>> {
@@ -41,6 +45,10 @@ impl<const NOM: u32, const DENOM: u32> InfraMonotonic for Instant<u64, NOM, DENO
         let p: u64 = p.into();
         Span::<Self::Duration>::new(p.micros(), t)
     }
+
+    fn to_micros(_freq: u32, dt: Self::Duration) -> u32 {
+        dt.to_micros().try_into().unwrap_or(u32::MAX)
+    }
 }
 
 impl<const NOM: u32, const DENOM: u32> Span<Duration<u64, NOM, DENOM>> {
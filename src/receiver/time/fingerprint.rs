@@ -0,0 +1,540 @@
+//! Fingerprinting of captured pulse trains
+//!
+//! Classifies the mark and space lengths of a frame recorded by [`RawCapture`] for remotes the
+//! crate doesn't know. The lengths are clustered, and the clusters tell the coding:
+//!
+//!  - Pulse distance: one mark length and two space lengths
+//!  - Pulse width: two mark lengths and one space length
+//!  - Bi-phase: marks and spaces of one, two or three units, with the shortest mark as long as
+//!    the shortest space
+//!
+//! A first mark and space not matching the data lengths is the header. Pulse width frames
+//! without a header, with ones twice as long as zeros, can't be told from bi-phase frames and
+//! are classified as bi-phase.
+//!
+//! The result is a [`Fingerprint`] with a [`Descriptor`] of the timing, the bits of the frame
+//! and the repeat period. The pulse distance and pulse width descriptors are the tables of the
+//! generic decoders and encoders in [`protocol::pulsedistance`] and [`protocol::pulsewidth`].
+//! The fingerprint can also decode other frames of the remote and encode bits into pulses for
+//! `Sender::load_raw`.
+//!
+//! The bit order can't be told from the pulses. Pulse distance and pulse width bits are assumed
+//! to be sent LSB first, bi-phase bits MSB first. The first bi-phase bit is assumed to be a one,
+//! as the start bits of Rc5 and Rc6.
+//!
+//! [`RawCapture`]: crate::receiver::RawCapture
+//! [`protocol::pulsedistance`]: crate::protocol::pulsedistance
+//! [`protocol::pulsewidth`]: crate::protocol::pulsewidth
+
+use crate::{
+    protocol::{
+        biphase::Polarity,
+        pulsedistance::{BitOrder, PulseDistance, Repeat},
+        pulsewidth::{Coding, PulseWidth},
+    },
+    receiver::RawFrame,
+};
+
+#[cfg(all(
+    test,
+    feature = "nec",
+    feature = "sirc",
+    feature = "rc5",
+    feature = "rc6"
+))]
+mod tests;
+
+/// Max number of distinct mark or space lengths in a frame
+const MAX_CLUSTERS: usize = 8;
+/// Max number of bi-phase half bits in a frame
+const MAX_HALVES: usize = 2 * 64 + 8;
+
+/// Timing of a captured frame
+#[derive(Copy, Clone, Debug)]
+pub enum Descriptor {
+    /// The bits are in the spaces
+    PulseDistance(PulseDistance),
+    /// The bits are in the marks
+    PulseWidth(PulseWidth),
+    /// The bits are in the order of the mark and the space
+    BiPhase(BiPhaseTiming),
+}
+
+/// Timing and frame layout of a bi-phase frame. Times in us
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BiPhaseTiming {
+    /// Header mark, 0 without a header
+    pub header_high: u32,
+    /// Header space, 0 without a header
+    pub header_low: u32,
+    /// Length of a half bit
+    pub unit: u32,
+    /// Number of bits in a frame
+    pub nbits: u8,
+    /// Order of the mark and space in a one
+    pub polarity: Polarity,
+    /// Mask of the bits sent with double width, bit 0 is the first transmitted bit
+    pub double_width: u64,
+}
+
+/// Classification of a captured frame
+#[derive(Copy, Clone, Debug)]
+pub struct Fingerprint {
+    /// Timing and frame layout. Without a header, the header lengths are 0
+    pub descriptor: Descriptor,
+    /// Shortest mark or space of the data, in us
+    pub unit: u32,
+    /// The bits of the frame, `None` if the frame has more than 64 bits or doesn't decode
+    pub bits: Option<u64>,
+    /// Time from the start of a frame to the start of the next, in us
+    pub repeat_period: Option<u32>,
+}
+
+impl Fingerprint {
+    /// Classify the first frame of a capture
+    pub fn classify(raw: &RawFrame) -> Option<Self> {
+        let mut fingerprint = Self::from_pulses(raw.frame())?;
+        fingerprint.repeat_period = raw.repeat_period();
+        Some(fingerprint)
+    }
+
+    /// Classify a frame of mark and space lengths in us, starting and ending with a mark
+    pub fn from_pulses(frame: &[u32]) -> Option<Self> {
+        if frame.len() < 5 || frame.len() & 0x1 == 0 {
+            return None;
+        }
+
+        let mut marks = Clusters::default();
+        let mut spaces = Clusters::default();
+        for (i, us) in frame.iter().enumerate().skip(2) {
+            if i & 0x1 == 0 {
+                marks.add(*us)?;
+            } else {
+                spaces.add(*us)?;
+            }
+        }
+
+        let header = if marks.find(frame[0]).is_some() && spaces.find(frame[1]).is_some() {
+            marks.add(frame[0])?;
+            spaces.add(frame[1])?;
+            None
+        } else {
+            Some((frame[0], frame[1]))
+        };
+        let (header_high, header_low) = header.unwrap_or((0, 0));
+        let data = if header.is_some() { &frame[2..] } else { frame };
+
+        let marks = marks.means();
+        let spaces = spaces.means();
+        let nbits = u8::try_from(data.len() / 2).unwrap_or(u8::MAX);
+
+        let descriptor = if let Some(unit) =
+            biphase_unit(marks.as_slice(), spaces.as_slice(), header.is_some())
+        {
+            // Frames without a header start after the space of the first half bit
+            let polarity = if header.is_some() {
+                Polarity::MarkSpace
+            } else {
+                Polarity::SpaceMark
+            };
+            let (nbits, double_width) = BiPhaseBits::new(data, unit, polarity)?.count()?;
+
+            Descriptor::BiPhase(BiPhaseTiming {
+                header_high,
+                header_low,
+                unit,
+                nbits,
+                polarity,
+                double_width,
+            })
+        } else {
+            match (marks.as_slice(), spaces.as_slice()) {
+                (&[data_high], &[zero_low, one_low]) => Descriptor::PulseDistance(PulseDistance {
+                    header_high,
+                    header_low,
+                    data_high,
+                    zero_low,
+                    one_low,
+                    nbits,
+                    order: BitOrder::Lsb,
+                    stop_bit: true,
+                    repeat: Repeat::Frame,
+                }),
+                (&[zero_high, one_high], &[low]) => Descriptor::PulseWidth(PulseWidth {
+                    header_high,
+                    header_low,
+                    zero_high,
+                    one_high,
+                    zero_low: low,
+                    one_low: low,
+                    // The last mark has a bit as well
+                    nbits: nbits.saturating_add(1),
+                    order: BitOrder::Lsb,
+                    coding: Coding::Mark,
+                }),
+                _ => return None,
+            }
+        };
+
+        let unit = marks.as_slice()[0].min(spaces.as_slice()[0]);
+
+        let mut fingerprint = Fingerprint {
+            descriptor,
+            unit,
+            bits: None,
+            repeat_period: None,
+        };
+        fingerprint.bits = fingerprint.decode(frame);
+        Some(fingerprint)
+    }
+
+    /// Number of bits in a frame
+    pub fn nbits(&self) -> u8 {
+        match &self.descriptor {
+            Descriptor::PulseDistance(pd) => pd.nbits,
+            Descriptor::PulseWidth(pw) => pw.nbits,
+            Descriptor::BiPhase(bp) => bp.nbits,
+        }
+    }
+
+    /// Decode a frame with the timing of the fingerprint. The bits are numbered in transmission
+    /// order, LSB first for pulse distance and pulse width and MSB first for bi-phase
+    pub fn decode(&self, frame: &[u32]) -> Option<u64> {
+        let (header_high, header_low) = self.header();
+        let data = if header_high == 0 {
+            frame
+        } else {
+            let (header, data) = (frame.get(..2)?, &frame[2..]);
+            if !close(header[0], header_high) || !close(header[1], header_low) {
+                return None;
+            }
+            data
+        };
+
+        if self.nbits() > 64 {
+            return None;
+        }
+
+        match &self.descriptor {
+            Descriptor::PulseDistance(pd) => {
+                if data.len() != 2 * usize::from(pd.nbits) + 1 {
+                    return None;
+                }
+
+                data.chunks(2)
+                    .take(usize::from(pd.nbits))
+                    .enumerate()
+                    .try_fold(0, |bits, (n, pulse)| {
+                        let one = nearest(pulse[1], pd.zero_low, pd.one_low)?;
+                        Some(bits | u64::from(one) << n)
+                    })
+            }
+            Descriptor::PulseWidth(pw) => {
+                if data.len() + 1 != 2 * usize::from(pw.nbits) {
+                    return None;
+                }
+
+                data.iter()
+                    .step_by(2)
+                    .enumerate()
+                    .try_fold(0, |bits, (n, mark)| {
+                        let one = nearest(*mark, pw.zero_high, pw.one_high)?;
+                        Some(bits | u64::from(one) << n)
+                    })
+            }
+            Descriptor::BiPhase(bp) => {
+                let mut halves = BiPhaseBits::new(data, bp.unit, bp.polarity)?;
+                let mut bits = 0;
+                let mut n = 0;
+
+                while let Some((one, _)) = halves.next_bit()? {
+                    bits = bits << 1 | u64::from(one);
+                    n += 1;
+                }
+
+                (n == bp.nbits).then_some(bits)
+            }
+        }
+    }
+
+    /// Encode `bits` into mark and space lengths in us, starting with a mark, as taken by
+    /// `Sender::load_raw`. Returns the number of lengths, pulses not fitting into the buffer are
+    /// dropped
+    pub fn encode(&self, bits: u64, buf: &mut [u32]) -> usize {
+        let mut pulses = Pulses::new(buf);
+        let (header_high, header_low) = self.header();
+        pulses.push(true, header_high);
+        pulses.push(false, header_low);
+
+        match &self.descriptor {
+            Descriptor::PulseDistance(pd) => {
+                for n in 0..pd.nbits {
+                    pulses.push(true, pd.data_high);
+                    let one = bit(bits, n);
+                    pulses.push(false, if one { pd.one_low } else { pd.zero_low });
+                }
+                pulses.push(true, pd.data_high);
+            }
+            Descriptor::PulseWidth(pw) => {
+                for n in 0..pw.nbits {
+                    let one = bit(bits, n);
+                    pulses.push(true, if one { pw.one_high } else { pw.zero_high });
+                    if n + 1 < pw.nbits {
+                        pulses.push(false, pw.zero_low);
+                    }
+                }
+            }
+            Descriptor::BiPhase(bp) => {
+                for n in 0..bp.nbits {
+                    let one = bit(bits, bp.nbits - 1 - n);
+                    let width = if n < 64 && bp.double_width & 1 << n != 0 {
+                        2
+                    } else {
+                        1
+                    };
+                    let mark_first = one == (bp.polarity == Polarity::MarkSpace);
+                    pulses.push(mark_first, width * bp.unit);
+                    pulses.push(!mark_first, width * bp.unit);
+                }
+            }
+        }
+
+        pulses.finish()
+    }
+
+    fn header(&self) -> (u32, u32) {
+        match &self.descriptor {
+            Descriptor::PulseDistance(pd) => (pd.header_high, pd.header_low),
+            Descriptor::PulseWidth(pw) => (pw.header_high, pw.header_low),
+            Descriptor::BiPhase(bp) => (bp.header_high, bp.header_low),
+        }
+    }
+}
+
+/// Bit `n` of `bits`
+fn bit(bits: u64, n: u8) -> bool {
+    bits.checked_shr(n.into())
+        .is_some_and(|bits| bits & 0x1 != 0)
+}
+
+/// Is `us` within 25 % of `len`
+fn close(us: u32, len: u32) -> bool {
+    us.abs_diff(len) <= len / 4
+}
+
+/// Is `us` closer to `one` than to `zero`, `None` if it's close to neither
+fn nearest(us: u32, zero: u32, one: u32) -> Option<bool> {
+    if !close(us, zero) && !close(us, one) {
+        return None;
+    }
+    Some(us.abs_diff(one) < us.abs_diff(zero))
+}
+
+/// The unit of a bi-phase frame with these mark and space lengths. Frames without a header can
+/// have one mark or space length, frames with a header need two of both
+fn biphase_unit(marks: &[u32], spaces: &[u32], header: bool) -> Option<u32> {
+    let min = if header { 2 } else { 1 };
+    if marks.len() < min || spaces.len() < min || !close(spaces[0], marks[0]) {
+        return None;
+    }
+
+    let unit = (marks[0] + spaces[0]) / 2;
+    let units = |us: &u32| (1..=3).find(|n| close(*us, n * unit));
+
+    let all_units = marks.iter().chain(spaces).all(|us| units(us).is_some());
+    let double = marks.iter().chain(spaces).any(|us| units(us) == Some(2));
+
+    (all_units && double).then_some(unit)
+}
+
+/// Lengths of the same kind, grouped by length
+#[derive(Default)]
+struct Clusters {
+    /// Sum and number of the lengths in the cluster
+    clusters: [(u32, u32); MAX_CLUSTERS],
+    len: usize,
+}
+
+/// Mean lengths of clusters, shortest first
+struct Means {
+    means: [u32; MAX_CLUSTERS],
+    len: usize,
+}
+
+impl Clusters {
+    /// The cluster `us` belongs to
+    fn find(&self, us: u32) -> Option<usize> {
+        self.clusters[..self.len]
+            .iter()
+            .position(|&(sum, count)| close(us, sum / count))
+    }
+
+    /// Add `us` to its cluster, `None` if there are too many clusters
+    fn add(&mut self, us: u32) -> Option<()> {
+        let index = match self.find(us) {
+            Some(index) => index,
+            None if self.len < MAX_CLUSTERS => {
+                self.len += 1;
+                self.len - 1
+            }
+            None => return None,
+        };
+
+        let (sum, count) = &mut self.clusters[index];
+        *sum = sum.saturating_add(us);
+        *count += 1;
+        Some(())
+    }
+
+    fn means(&self) -> Means {
+        let mut means = [0; MAX_CLUSTERS];
+        for (mean, (sum, count)) in means.iter_mut().zip(&self.clusters[..self.len]) {
+            *mean = sum / count;
+        }
+        means[..self.len].sort_unstable();
+
+        Means {
+            means,
+            len: self.len,
+        }
+    }
+}
+
+impl Means {
+    fn as_slice(&self) -> &[u32] {
+        &self.means[..self.len]
+    }
+}
+
+/// The half bits of a bi-phase frame
+struct BiPhaseBits {
+    /// Levels of the half bits, true for a mark
+    halves: [bool; MAX_HALVES],
+    len: usize,
+    pos: usize,
+    /// Bits read
+    n: u8,
+    polarity: Polarity,
+}
+
+impl BiPhaseBits {
+    /// Split the lengths of `data` into half bits. `None` if a length isn't one to three units
+    /// or the frame is too long
+    fn new(data: &[u32], unit: u32, polarity: Polarity) -> Option<Self> {
+        let mut bits = BiPhaseBits {
+            halves: [false; MAX_HALVES],
+            len: 0,
+            pos: 0,
+            n: 0,
+            polarity,
+        };
+
+        // The first half bit of a space-mark one is the space before the frame
+        if polarity == Polarity::SpaceMark {
+            bits.len = 1;
+        }
+
+        for (i, us) in data.iter().enumerate() {
+            let units = (1..=3).find(|n| close(*us, n * unit))?;
+            for _ in 0..units {
+                *bits.halves.get_mut(bits.len)? = i & 0x1 == 0;
+                bits.len += 1;
+            }
+        }
+
+        Some(bits)
+    }
+
+    /// The half bit at `pos`, the frame is followed by a space
+    fn half(&self, pos: usize) -> bool {
+        pos < self.len && self.halves[pos]
+    }
+
+    /// The next bit and if it has double width. `Some(None)` at the end of the frame, `None` if
+    /// the half bits aren't a bit
+    fn next_bit(&mut self) -> Option<Option<(bool, bool)>> {
+        if self.pos >= self.len {
+            return Some(None);
+        }
+
+        let (first, second) = (self.half(self.pos), self.half(self.pos + 1));
+        let double = first == second;
+        if double {
+            // A double width bit, two equal half bits followed by two of the other level
+            let (third, fourth) = (self.half(self.pos + 2), self.half(self.pos + 3));
+            if third == first || fourth != third {
+                return None;
+            }
+            self.pos += 4;
+        } else {
+            self.pos += 2;
+        }
+
+        self.n = self.n.checked_add(1)?;
+        Some(Some((
+            first == (self.polarity == Polarity::MarkSpace),
+            double,
+        )))
+    }
+
+    /// Number of bits and the mask of the double width bits
+    fn count(mut self) -> Option<(u8, u64)> {
+        let mut double_width = 0;
+
+        while let Some((_, double)) = self.next_bit()? {
+            if double && self.n <= 64 {
+                double_width |= 1 << (self.n - 1);
+            }
+        }
+
+        Some((self.n, double_width))
+    }
+}
+
+/// Writes mark and space lengths into a buffer, merging lengths of the same level
+struct Pulses<'a> {
+    buf: &'a mut [u32],
+    len: usize,
+    /// Level of the last length
+    mark: bool,
+}
+
+impl<'a> Pulses<'a> {
+    fn new(buf: &'a mut [u32]) -> Self {
+        Pulses {
+            buf,
+            len: 0,
+            mark: false,
+        }
+    }
+
+    fn push(&mut self, mark: bool, us: u32) {
+        if us == 0 || (self.len == 0 && !mark) {
+            // Nothing to send before the first mark
+            return;
+        }
+
+        if self.len > 0 && mark == self.mark {
+            if let Some(last) = self.buf.get_mut(self.len - 1) {
+                *last += us;
+            }
+            return;
+        }
+
+        if let Some(b) = self.buf.get_mut(self.len) {
+            *b = us;
+        }
+        self.len += 1;
+        self.mark = mark;
+    }
+
+    /// Drop the trailing space, returns the number of lengths in the buffer
+    fn finish(self) -> usize {
+        let len = if self.mark {
+            self.len
+        } else {
+            self.len.saturating_sub(1)
+        };
+        len.min(self.buf.len())
+    }
+}
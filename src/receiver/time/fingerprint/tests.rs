@@ -0,0 +1,198 @@
+use std::vec::Vec;
+
+use crate::{
+    protocol::{
+        biphase::Polarity,
+        nec::{NecCommand, NecCommandVariant},
+        rc5::Rc5Command,
+        rc6::Rc6Command,
+        sirc::SircCommand,
+        Nec, Rc5, Rc6, Sirc,
+    },
+    receiver::{
+        time::fingerprint::{BiPhaseTiming, Descriptor, Fingerprint},
+        RawCapture,
+    },
+    sender::{ProtocolEncoder, PulsedataBuffer},
+};
+
+/// Mark and space lengths in us of an encoded command, without the leading zero
+fn pulses<P: ProtocolEncoder<1_000_000>>(cmd: &P::Cmd) -> Vec<u32> {
+    let mut ptb = PulsedataBuffer::<256>::new();
+    ptb.load::<P, 1_000_000>(cmd);
+    let mut pulses = Vec::from(&ptb.buffer()[1..]);
+    // Drop the gap after the frame
+    if pulses.len() & 0x1 == 0 {
+        pulses.pop();
+    }
+    pulses
+}
+
+/// Encode the bits of the fingerprint and compare with the frame
+fn assert_reencodes(fp: &Fingerprint, frame: &[u32]) {
+    let mut buf = [0; 256];
+    let len = fp.encode(fp.bits.unwrap(), &mut buf);
+    assert_eq!(&buf[..len], frame);
+}
+
+#[test]
+fn nec_is_pulse_distance() {
+    let cmd = NecCommand {
+        addr: 0x12,
+        cmd: 0x34,
+        repeat: false,
+    };
+    let frame = pulses::<Nec>(&cmd);
+    let fp = Fingerprint::from_pulses(&frame).unwrap();
+
+    let Descriptor::PulseDistance(pd) = fp.descriptor else {
+        panic!("{:?}", fp.descriptor);
+    };
+    assert_eq!((pd.header_high, pd.header_low), (9000, 4500));
+    assert_eq!((pd.data_high, pd.zero_low, pd.one_low), (560, 560, 1690));
+    assert_eq!(pd.nbits, 32);
+    assert_eq!(fp.unit, 560);
+    assert_eq!(fp.bits, Some(cmd.pack()));
+
+    assert_reencodes(&fp, &frame);
+}
+
+#[test]
+fn sirc_is_pulse_width() {
+    let cmd = SircCommand::new(0x01, 0x15);
+    let frame = pulses::<Sirc>(&cmd);
+    let fp = Fingerprint::from_pulses(&frame).unwrap();
+
+    let Descriptor::PulseWidth(pw) = fp.descriptor else {
+        panic!("{:?}", fp.descriptor);
+    };
+    assert_eq!((pw.header_high, pw.header_low), (2400, 600));
+    assert_eq!((pw.zero_high, pw.one_high, pw.zero_low), (600, 1200, 600));
+    assert_eq!(fp.nbits(), 12);
+    assert_eq!(fp.bits, Some(0x15 | 0x01 << 7));
+
+    assert_reencodes(&fp, &frame);
+}
+
+#[test]
+fn rc5_is_biphase() {
+    for cmd in [
+        Rc5Command::new(20, 9, false),
+        Rc5Command::new(0x1F, 0x2A, true),
+        Rc5Command::new(0, 0, false),
+        // Rc5X, the second start bit is cleared
+        Rc5Command::new(5, 100, false),
+    ] {
+        let frame = pulses::<Rc5>(&cmd);
+        let fp = Fingerprint::from_pulses(&frame).unwrap();
+
+        let Descriptor::BiPhase(bp) = fp.descriptor else {
+            panic!("{:?}", fp.descriptor);
+        };
+        assert_eq!(
+            bp,
+            BiPhaseTiming {
+                header_high: 0,
+                header_low: 0,
+                unit: 889,
+                nbits: 14,
+                polarity: Polarity::SpaceMark,
+                double_width: 0,
+            }
+        );
+        assert_eq!(fp.bits, Some(u64::from(cmd.pack())));
+
+        assert_reencodes(&fp, &frame);
+    }
+}
+
+#[test]
+fn rc6_is_biphase_with_header() {
+    let cmd = Rc6Command::new(70, 20);
+    let frame = pulses::<Rc6>(&cmd);
+    let fp = Fingerprint::from_pulses(&frame).unwrap();
+
+    let Descriptor::BiPhase(bp) = fp.descriptor else {
+        panic!("{:?}", fp.descriptor);
+    };
+    assert_eq!((bp.header_high, bp.header_low), (6 * 444, 2 * 444));
+    assert_eq!(bp.unit, 444);
+    assert_eq!(bp.polarity, Polarity::MarkSpace);
+    // Start bit, three mode bits, the double width toggle bit, address and command
+    assert_eq!(bp.nbits, 21);
+    assert_eq!(bp.double_width, 1 << 4);
+    assert_eq!(fp.bits, Some(1 << 20 | 70 << 8 | 20));
+
+    assert_reencodes(&fp, &frame);
+}
+
+#[test]
+fn jitter() {
+    let cmd = NecCommand {
+        addr: 0xA5,
+        cmd: 0x0F,
+        repeat: false,
+    };
+    let mut frame = pulses::<Nec>(&cmd);
+
+    // Marks are received longer and spaces shorter
+    for (i, us) in frame.iter_mut().enumerate() {
+        let delta = *us / 10 - (i as u32 % 3) * 10;
+        if i & 0x1 == 0 {
+            *us += delta;
+        } else {
+            *us -= delta;
+        }
+    }
+
+    let fp = Fingerprint::from_pulses(&frame).unwrap();
+    assert!(matches!(fp.descriptor, Descriptor::PulseDistance(_)));
+    assert_eq!(fp.bits, Some(cmd.pack()));
+
+    // Other frames of the remote decode with the timing
+    let other = NecCommand {
+        addr: 0xA5,
+        cmd: 0x10,
+        repeat: false,
+    };
+    assert_eq!(fp.decode(&pulses::<Nec>(&other)), Some(other.pack()));
+}
+
+#[test]
+fn repeat_period() {
+    let cmd = SircCommand::new(0x01, 0x15);
+    let frame = pulses::<Sirc>(&cmd);
+    let gap = 45_000 - frame.iter().sum::<u32>();
+
+    let mut buf = [0; 128];
+    let mut capture: RawCapture = RawCapture::new(1_000_000, &mut buf);
+    let mut fingerprint = None;
+
+    // Leading zero, three frames and the idle gap
+    let mut edges = std::vec![0];
+    for _ in 0..3 {
+        edges.extend_from_slice(&frame);
+        edges.push(gap);
+    }
+    *edges.last_mut().unwrap() = 200_000;
+
+    for (i, dt) in edges.iter().enumerate() {
+        if let Some(raw) = capture.event(*dt, i & 0x1 == 0) {
+            fingerprint = Fingerprint::classify(&raw);
+        }
+    }
+
+    let fp = fingerprint.unwrap();
+    assert_eq!(fp.repeat_period, Some(45_000));
+    assert_eq!(fp.bits, Some(0x15 | 0x01 << 7));
+}
+
+#[test]
+fn unknown() {
+    // Too short
+    assert!(Fingerprint::from_pulses(&[9000, 2250, 560]).is_none());
+    // Three space lengths
+    assert!(Fingerprint::from_pulses(&[500, 500, 500, 1000, 500, 1500, 500, 2000, 500]).is_none());
+    // All marks and spaces the same length, as an Rc5 frame of all ones
+    assert!(Fingerprint::from_pulses(&[500, 500, 500, 500, 500, 500, 500]).is_none());
+}
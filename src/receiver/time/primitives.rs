@@ -16,6 +16,10 @@ impl InfraMonotonic for u32 {
     fn create_span(freq: u32, p: u32, t: u32) -> Span<Self::Duration> {
         Span::<u32>::scaled(p, freq, t)
     }
+
+    fn to_micros(freq: u32, dt: Self::Duration) -> u32 {
+        u64::to_micros(freq, dt.into())
+    }
 }
 
 impl InfraMonotonic for u64 {
@@ -31,6 +35,11 @@ impl InfraMonotonic for u64 {
     fn create_span(freq: u32, p: u32, t: u32) -> Span<Self::Duration> {
         Span::<u64>::scaled(p, freq, t)
     }
+
+    fn to_micros(freq: u32, dt: Self::Duration) -> u32 {
+        let us = u128::from(dt) * 1_000_000 / u128::from(freq);
+        us.try_into().unwrap_or(u32::MAX)
+    }
 }
 
 impl Span<u32> {
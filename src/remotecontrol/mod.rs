@@ -60,6 +60,7 @@ pub enum DeviceType {
     DVDPlayer,
     CDPlayer,
     BluRayPlayer,
+    Soundbar,
 }
 
 #[allow(non_camel_case_types)]
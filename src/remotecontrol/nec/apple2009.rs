@@ -9,6 +9,9 @@ use crate::{
 #[derive(Debug, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// Apple Remote
+///
+/// Commands from all device ids are decoded. When encoding, `ADDRESS` is used as device id,
+/// use [`Apple2009::encode_with_id`] to emulate a remote paired with another id.
 pub struct Apple2009;
 
 // (page, cmd) -> Button
@@ -38,4 +41,18 @@ impl RemoteControlModel for Apple2009 {
             .find(|(c, _b)| c == &(cmd.command_page, cmd.command))
             .map(|(_, b)| *b)
     }
+
+    fn encode(button: &Action) -> Option<AppleNecCommand> {
+        Self::encode_with_id(button, Self::ADDRESS as u8)
+    }
+}
+
+impl Apple2009 {
+    /// Encode a button into a command with `device_id` as pairing id
+    pub fn encode_with_id(button: &Action, device_id: u8) -> Option<AppleNecCommand> {
+        BUTTONS
+            .iter()
+            .find(|(_, b)| b == button)
+            .map(|((page, cmd), _)| AppleNecCommand::new(*page, *cmd, device_id))
+    }
 }
@@ -0,0 +1,58 @@
+use Action::*;
+
+use crate::{
+    protocol::rc6::Rc6Command,
+    remotecontrol::{Action, DeviceType, RemoteControlModel},
+    ProtocolId,
+};
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Windows Media Center remote. Rc6 mode 6A with 32 bit payload
+pub struct MceRemote;
+
+impl RemoteControlModel for MceRemote {
+    const MODEL: &'static str = "Windows Media Center Remote";
+    const DEVTYPE: DeviceType = DeviceType::Generic;
+    const PROTOCOL: ProtocolId = ProtocolId::Rc6;
+    /// Customer code 0x800F, address 0x04
+    const ADDRESS: u32 = 0x800F04;
+    type Cmd = Rc6Command;
+    const BUTTONS: &'static [(u32, Action)] = &[
+        (0x00, Zero),
+        (0x01, One),
+        (0x02, Two),
+        (0x03, Three),
+        (0x04, Four),
+        (0x05, Five),
+        (0x06, Six),
+        (0x07, Seven),
+        (0x08, Eight),
+        (0x09, Nine),
+        (0x0C, Power),
+        (0x0E, Mute),
+        (0x0F, Info),
+        (0x10, VolumeUp),
+        (0x11, VolumeDown),
+        (0x12, ChannelListNext),
+        (0x13, ChannelListPrev),
+        (0x14, Forward),
+        (0x15, Rewind),
+        (0x16, Play),
+        (0x18, Paus),
+        (0x19, Stop),
+        (0x1A, Next),
+        (0x1B, Prev),
+        (0x1E, Up),
+        (0x1F, Down),
+        (0x20, Left),
+        (0x21, Right),
+        (0x22, Enter),
+        (0x23, Return),
+        (0x5A, Teletext),
+        (0x5B, Red),
+        (0x5C, Green),
+        (0x5D, Yellow),
+        (0x5E, Blue),
+    ];
+}
@@ -1 +1,2 @@
-
+mod mce;
+pub use mce::MceRemote;
@@ -1,2 +1,4 @@
 mod blurayplayer;
+mod soundbar;
 pub use blurayplayer::SamsungBluRayPlayer;
+pub use soundbar::SamsungSoundbar;
@@ -0,0 +1,33 @@
+use Action::*;
+
+use crate::{
+    protocol::sbp::Samsung36Command,
+    remotecontrol::{Action, DeviceType, RemoteControlModel},
+    ProtocolId,
+};
+
+#[derive(Debug, Default)]
+/// Samsung soundbar remote, Samsung36 protocol
+pub struct SamsungSoundbar;
+
+impl RemoteControlModel for SamsungSoundbar {
+    const MODEL: &'static str = "Samsung Soundbar";
+    const DEVTYPE: DeviceType = DeviceType::Soundbar;
+    const PROTOCOL: ProtocolId = ProtocolId::Sbp;
+    const ADDRESS: u32 = 0x5343;
+    type Cmd = Samsung36Command;
+    const BUTTONS: &'static [(u32, Action)] = &[
+        (0x1E, Power),
+        (0x20, Source),
+        (0x2E, VolumeUp),
+        (0x2F, VolumeDown),
+        (0x1F, VolumeMute),
+        (0x2C, Play_Pause),
+        (0x26, Up),
+        (0x27, Down),
+        (0x28, Left),
+        (0x29, Right),
+        (0x2A, Enter),
+        (0x2B, Setup),
+    ];
+}
@@ -1,4 +1,4 @@
-use crate::sender::ProtocolEncoder;
+use crate::{protocol::utils::scale_with_samplerate, sender::ProtocolEncoder};
 
 pub(crate) struct PulsedataBuffer<const S: usize> {
     pub buf: [u32; S],
@@ -22,6 +22,23 @@ impl<const S: usize> PulsedataBuffer<S> {
         self.offset += len;
     }
 
+    /// Load mark and space lengths in us, starting with a mark. Pulses not fitting into the
+    /// buffer are dropped
+    pub fn load_raw<const F: u32>(&mut self, pulses: &[u32]) {
+        let free = &mut self.buf[self.offset..];
+        let Some((first, rest)) = free.split_first_mut() else {
+            return;
+        };
+
+        *first = 0;
+        let n = pulses.len().min(rest.len());
+        for (b, us) in rest.iter_mut().zip(&pulses[..n]) {
+            *b = scale_with_samplerate(*us, F);
+        }
+
+        self.offset += 1 + n;
+    }
+
     pub fn get(&self, index: usize) -> Option<u32> {
         self.buf.get(index).cloned()
     }
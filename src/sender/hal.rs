@@ -31,6 +31,14 @@ where
         }
     }
 
+    /// Load mark and space lengths in us, as recorded by `RawCapture`
+    pub fn load_raw(&mut self, pulses: &[u32]) {
+        if self.buffer.status == Status::Idle {
+            self.buffer.load_raw::<F>(pulses);
+            self.counter = 0;
+        }
+    }
+
     pub fn buffer(&self) -> &[u32] {
         self.buffer.buffer()
     }
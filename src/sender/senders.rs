@@ -32,6 +32,12 @@ impl<const S: usize> PulsedataSender<S> {
         self.ptb.load::<Proto, F>(c);
     }
 
+    /// Load mark and space lengths in us, as recorded by `RawCapture`
+    pub fn load_raw<const F: u32>(&mut self, pulses: &[u32]) {
+        self.reset();
+        self.ptb.load_raw::<F>(pulses);
+    }
+
     pub fn tick(&mut self, ts: u32) -> Status {
         if let Some(dist) = self.ptb.get(self.pos) {
             let delta_ts = ts.wrapping_sub(self.ts_lastedge);
@@ -42,3 +42,14 @@ impl InputPin for DummyEmbeddedHalPin {
         Ok(false)
     }
 }
+
+#[cfg(feature = "rc5")]
+#[test]
+fn receiver_pin() {
+    use infrared::{protocol::Rc5, Receiver};
+
+    let mut r: Receiver<Rc5, DummyEmbeddedHalPin> =
+        infrared::receiver().rc5().pin(DummyEmbeddedHalPin).build();
+
+    assert_eq!(r.event(40), Ok(None));
+}
@@ -33,7 +33,6 @@ fn polled_rc5() {
         Some(Rc5Command {
             addr: 20,
             cmd: 1,
-            start: 3,
             toggle: false
         })
     );